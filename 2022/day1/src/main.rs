@@ -1,40 +1,60 @@
-fn get_elf_capacities(input: &str) -> Vec<usize> {
-    let mut result = Vec::new();
+use std::io::BufRead;
 
+use aoc_runner::Answer;
+
+fn for_each_elf_capacity<R: BufRead>(reader: R, mut f: impl FnMut(usize)) {
     let mut sum = 0;
-    for line in input.lines() {
+    aoc_runner::for_each_line(reader, |line| {
         if line.is_empty() {
-            result.push(sum);
+            f(sum);
             sum = 0;
         } else {
             sum += line.parse::<usize>().unwrap();
         }
-    }
+    });
 
-    result.push(sum);
+    f(sum);
+}
 
-    result
+fn part_1_reader<R: BufRead>(reader: R) -> usize {
+    let mut max = 0;
+    for_each_elf_capacity(reader, |capacity| max = max.max(capacity));
+    max
 }
 
-fn part_1(input: &str) -> usize {
-    get_elf_capacities(input).into_iter().max().unwrap()
+fn part_2_reader<R: BufRead>(reader: R) -> usize {
+    // Only the three largest so far, smallest first
+    let mut top = [0; 3];
+    for_each_elf_capacity(reader, |capacity| {
+        if capacity > top[0] {
+            top[0] = capacity;
+            top.sort();
+        }
+    });
+
+    top.iter().sum()
 }
 
-fn part_2(input: &str) -> usize {
-    let mut capacities = get_elf_capacities(input);
-    capacities.sort_by(|a, b| b.cmp(&a));
-    
-    capacities.into_iter()
-        .take(3)
-        .sum()
+fn part_1(input: &str) -> Answer {
+    part_1_reader(input.as_bytes()).into()
 }
 
+fn part_2(input: &str) -> Answer {
+    part_2_reader(input.as_bytes()).into()
+}
+
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    let result = part_1(include_str!("input.txt"));
-    println!("Part 1: {result}");
+    if let Some(path) = aoc_runner::input_path() {
+        aoc_runner::answer!(1, part_1_reader(aoc_runner::open_input(&path)));
+        aoc_runner::answer!(2, part_2_reader(aoc_runner::open_input(&path)));
+        return;
+    }
+
+    aoc_runner::answer!(1, part_1(include_str!("input.txt")));
 
-    let result = part_2(include_str!("input.txt"));
-    println!("Part 2: {result}");
+    aoc_runner::answer!(2, part_2(include_str!("input.txt")));
 }
 
 #[cfg(test)]
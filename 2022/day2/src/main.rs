@@ -1,3 +1,7 @@
+use std::io::BufRead;
+
+use aoc_runner::Answer;
+
 fn get_winning_play(other: char) -> char {
     match other {
         'A' => { 'Y' },
@@ -55,31 +59,40 @@ fn get_score_pt2(a: char, b: char) -> usize {
     get_score(a, hand)
 }
 
-fn get_rounds(input: &str) -> Vec<(char, char)> {
-    input.lines()
-        .map(|l| l.split_once(" ").unwrap())
-        .map(|l| (l.0.chars().next().unwrap(), l.1.chars().next().unwrap()))
-        .collect()
+fn parse_round(line: &str) -> (char, char) {
+    let l = line.split_once(" ").unwrap();
+    (l.0.chars().next().unwrap(), l.1.chars().next().unwrap())
+}
+
+fn get_total_score<R: BufRead>(reader: R, score: fn(char, char) -> usize) -> usize {
+    let mut total = 0;
+    aoc_runner::for_each_line(reader, |line| {
+        let round = parse_round(line);
+        total += score(round.0, round.1);
+    });
+    total
 }
 
-fn part_1(input: &str) -> usize {
-    get_rounds(input).into_iter()
-        .map(|r| get_score(r.0, r.1))
-        .sum()
+fn part_1(input: &str) -> Answer {
+    get_total_score(input.as_bytes(), get_score).into()
 }
 
-fn part_2(input: &str) -> usize {
-    get_rounds(input).into_iter()
-        .map(|r| get_score_pt2(r.0, r.1))
-        .sum()
+fn part_2(input: &str) -> Answer {
+    get_total_score(input.as_bytes(), get_score_pt2).into()
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    let result = part_1(include_str!("input.txt"));
-    println!("Part 1: {result}");
+    if let Some(path) = aoc_runner::input_path() {
+        aoc_runner::answer!(1, get_total_score(aoc_runner::open_input(&path), get_score));
+        aoc_runner::answer!(2, get_total_score(aoc_runner::open_input(&path), get_score_pt2));
+        return;
+    }
+
+    aoc_runner::answer!(1, part_1(include_str!("input.txt")));
 
-    let result = part_2(include_str!("input.txt"));
-    println!("Part 2: {result}");
+    aoc_runner::answer!(2, part_2(include_str!("input.txt")));
 }
 
 #[cfg(test)]
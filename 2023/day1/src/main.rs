@@ -1,70 +1,67 @@
-use std::{env, fs};
+mod scanner;
 
-fn parse_digit_word(line: &str) -> i32 {
-    let numbers = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+use std::{env, io::BufRead, path::PathBuf};
 
-    for (i, name) in numbers.iter().enumerate() {
-        if line.starts_with(name) {
-            return i as i32;
-        }
-    }
-    -1
-}
+use aoc_runner::Answer;
+use scanner::{Scanner, Vocabulary, ENGLISH, ENGLISH_TEENS};
 
-fn parse_digits(contents: &str, include_digit_names: bool) -> i32 {
-    let mut result = 0;
+fn parse_digits<R: BufRead>(reader: R, part: u32, scanner: &Scanner) -> Answer {
+    let mut result : u64 = 0;
+    let mut line_number = 0;
 
-    for line in contents.lines() {
-        //println!("Line: {line}");
-        let mut first_digit = -1;
-        let mut last_digit = -1;
+    aoc_runner::for_each_line(reader, |line| {
+        line_number += 1;
+        match scanner.calibration_value(line) {
+            Some(value) => result += value as u64,
+            None if line.is_empty() => {},
+            None => eprintln!("warning: part {part}: line {line_number} has no digits: {line}"),
+        }
+    });
 
-        for (i, char) in line.chars().enumerate() {
-            //println!(" + i: {i}, Char: {char}");
+    result.into()
+}
 
-            let mut digit = -1;
-            if char.is_numeric() {
-                digit = char as i32 - 0x30;
-                //println!("  + Found digit {digit}")
-            } else if include_digit_names {
-                let digit_word = &line[i..];
-                //println!("  + Digit Word: {digit_word}");
-                digit = parse_digit_word(digit_word);
-                //println!("    + Parsed: {digit}");
-            }
+// Part 2 reads the digit words from `--vocabulary <file>` (one `word value` per line) when given,
+// on top of English, and `--teens` adds ten to nineteen
+fn word_vocabulary() -> Vocabulary {
+    let mut vocabulary = Vocabulary::digits().with_words(&ENGLISH);
+    if env::args().any(|a| a == "--teens") {
+        vocabulary = vocabulary.with_words(&ENGLISH_TEENS);
+    }
 
-            if digit != -1 {
-                if first_digit == -1 {
-                    first_digit = digit;
-                }
+    if let Some(path) = aoc_runner::arg_value("--vocabulary") {
+        let text = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {path}: {e}"));
+        vocabulary = vocabulary.parse(&text).unwrap_or_else(|e| panic!("Invalid vocabulary {path}: {e}"));
+    }
+    vocabulary
+}
 
-                last_digit = digit;
-            }
-        }
+// Flags followed by a value, which is never the input file
+const VALUE_FLAGS : [&str; 3] = ["--input", "--vocabulary", "--explain-json"];
 
-        //println!("Line: {line} | First: {first_digit}, Last: {last_digit}");
-        if first_digit != -1 {
-            assert_ne!(last_digit, -1);
-            let value = (first_digit * 10) + last_digit;
-            result += value;
+// The first argument that is neither a flag nor a flag's value
+fn positional_input() -> Option<PathBuf> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if VALUE_FLAGS.contains(&arg.as_str()) {
+            args.next();
+        } else if !arg.starts_with("--") {
+            return Some(PathBuf::from(arg));
         }
     }
-
-    result
+    None
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let file_name = &args[1];
-    let contents = fs::read_to_string(file_name)
-        .expect("Should have read the file");
+    // The file is streamed line by line, so it can be arbitrarily large
+    let file_name = aoc_runner::input_path()
+        .or_else(positional_input)
+        .expect("Expected an input file, either --input <path> or as an argument");
 
-    {
-        let result = parse_digits(contents.as_str(), false);
-        println!("Result [Digits Only]: {result}");
-    }
-    {
-        let result = parse_digits(contents.as_str(), true);
-        println!("Result [Digit Names]: {result}");
-    }
+    let digits = Scanner::new(&Vocabulary::digits());
+    let words = Scanner::new(&word_vocabulary());
+    aoc_runner::answer!(1, parse_digits(aoc_runner::open_input(&file_name), 1, &digits));
+    aoc_runner::answer!(2, parse_digits(aoc_runner::open_input(&file_name), 2, &words));
 }
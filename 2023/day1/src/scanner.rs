@@ -0,0 +1,228 @@
+use std::collections::{HashMap, VecDeque};
+
+pub const ENGLISH : [(&str, u32); 10] = [
+    ("zero", 0), ("one", 1), ("two", 2), ("three", 3), ("four", 4),
+    ("five", 5), ("six", 6), ("seven", 7), ("eight", 8), ("nine", 9),
+];
+
+pub const ENGLISH_TEENS : [(&str, u32); 10] = [
+    ("ten", 10), ("eleven", 11), ("twelve", 12), ("thirteen", 13), ("fourteen", 14),
+    ("fifteen", 15), ("sixteen", 16), ("seventeen", 17), ("eighteen", 18), ("nineteen", 19),
+];
+
+// The words a line can spell its digits with, and the number each one stands for
+#[derive(Debug, Clone)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+impl Vocabulary {
+    // Just the digits themselves, 0-9
+    pub fn digits() -> Self {
+        Vocabulary { words: (0..10).map(|d| (d.to_string(), d)).collect() }
+    }
+
+    pub fn with_words(mut self, words: &[(&str, u32)]) -> Self {
+        self.words.extend(words.iter().map(|(w, v)| (w.to_string(), *v)));
+        self
+    }
+
+    // One `word value` pair per line, e.g. `drei 3` or `quince 15`
+    pub fn parse(self, text: &str) -> Result<Self, String> {
+        let mut words = Vec::new();
+        for (i, line) in text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let mut parts = line.split_whitespace();
+            let (Some(word), Some(value), None) = (parts.next(), parts.next(), parts.next()) else {
+                return Err(format!("line {}: expected 'word value', got '{line}'", i + 1));
+            };
+            let value = value.parse().map_err(|_| format!("line {}: '{value}' is not a number", i + 1))?;
+            words.push((word, value));
+        }
+        Ok(self.with_words(&words))
+    }
+}
+
+// Aho-Corasick automaton over a set of byte patterns. Working on bytes keeps every position a
+// valid byte offset into the line, whatever characters it contains.
+#[derive(Debug)]
+struct Automaton {
+    transitions: Vec<HashMap<u8, usize>>,
+    fail: Vec<usize>,
+    // Every pattern ending at a state, including those reached through its fail links
+    outputs: Vec<Vec<usize>>,
+    lengths: Vec<usize>,
+    longest: usize,
+}
+impl Automaton {
+    fn new(patterns: &[Vec<u8>]) -> Self {
+        let mut automaton = Automaton {
+            transitions: vec![HashMap::new()],
+            fail: vec![0],
+            outputs: vec![Vec::new()],
+            lengths: patterns.iter().map(|p| p.len()).collect(),
+            longest: patterns.iter().map(|p| p.len()).max().unwrap_or(0),
+        };
+
+        for (index, pattern) in patterns.iter().enumerate() {
+            let mut state = 0;
+            for &byte in pattern {
+                state = match automaton.transitions[state].get(&byte) {
+                    Some(&next) => next,
+                    None => {
+                        let next = automaton.transitions.len();
+                        automaton.transitions.push(HashMap::new());
+                        automaton.fail.push(0);
+                        automaton.outputs.push(Vec::new());
+                        automaton.transitions[state].insert(byte, next);
+                        next
+                    }
+                };
+            }
+            automaton.outputs[state].push(index);
+        }
+
+        // Breadth first, so a state's fail link is always finished before its children need it
+        let mut queue : VecDeque<usize> = automaton.transitions[0].values().copied().collect();
+        while let Some(state) = queue.pop_front() {
+            let children : Vec<(u8, usize)> = automaton.transitions[state].iter().map(|(&b, &s)| (b, s)).collect();
+            for (byte, child) in children {
+                let fail = automaton.next(automaton.fail[state], byte);
+                automaton.fail[child] = fail;
+                let inherited = automaton.outputs[fail].clone();
+                automaton.outputs[child].extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        automaton
+    }
+
+    fn next(&self, mut state: usize, byte: u8) -> usize {
+        loop {
+            if let Some(&next) = self.transitions[state].get(&byte) {
+                return next;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.fail[state];
+        }
+    }
+
+    // The match starting earliest, the longest one if several start there, as (start, pattern)
+    fn leftmost(&self, text: impl Iterator<Item = u8>) -> Option<(usize, usize)> {
+        let mut best : Option<(usize, usize)> = None;
+        let mut state = 0;
+
+        for (i, byte) in text.enumerate() {
+            let end = i + 1;
+            // Nothing ending from here on can start at or before the best match
+            if best.is_some_and(|(start, _)| end > start + self.longest) {
+                break;
+            }
+
+            state = self.next(state, byte);
+            for &pattern in &self.outputs[state] {
+                let start = end - self.lengths[pattern];
+                let better = match best {
+                    None => true,
+                    Some((best_start, best_pattern)) => start < best_start
+                        || (start == best_start && self.lengths[pattern] > self.lengths[best_pattern]),
+                };
+                if better {
+                    best = Some((start, pattern));
+                }
+            }
+        }
+        best
+    }
+}
+
+// A word found in a line, with its byte range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+
+// Finds the first word in a line scanning forward, and the last by scanning the line backward
+// through a second automaton of the reversed words. Overlapping words such as `oneight` give
+// `one` first and `eight` last.
+#[derive(Debug)]
+pub struct Scanner {
+    forward: Automaton,
+    backward: Automaton,
+    words: Vec<(usize, u32)>,
+}
+impl Scanner {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let patterns : Vec<Vec<u8>> = vocabulary.words.iter().map(|(w, _)| w.as_bytes().to_vec()).collect();
+        let reversed : Vec<Vec<u8>> = patterns.iter().map(|p| p.iter().rev().copied().collect()).collect();
+
+        Scanner {
+            forward: Automaton::new(&patterns),
+            backward: Automaton::new(&reversed),
+            words: vocabulary.words.iter().map(|(w, v)| (w.len(), *v)).collect(),
+        }
+    }
+
+    pub fn first(&self, line: &str) -> Option<Match> {
+        let (start, pattern) = self.forward.leftmost(line.bytes())?;
+        let (length, value) = self.words[pattern];
+        Some(Match { start, end: start + length, value })
+    }
+
+    pub fn last(&self, line: &str) -> Option<Match> {
+        let (from_end, pattern) = self.backward.leftmost(line.bytes().rev())?;
+        let (length, value) = self.words[pattern];
+        let end = line.len() - from_end;
+        Some(Match { start: end - length, end, value })
+    }
+
+    // The first digit of the first word's value followed by the last digit of the last word's,
+    // so `seventeen` counts as 1 at the start of a line and 7 at the end
+    pub fn calibration_value(&self, line: &str) -> Option<u32> {
+        let first = self.first(line)?.value.to_string();
+        let last = self.last(line)?.value;
+        let first_digit = first.as_bytes()[0] - b'0';
+        Some(first_digit as u32 * 10 + last % 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_words() {
+        let scanner = Scanner::new(&Vocabulary::digits().with_words(&ENGLISH));
+
+        assert_eq!(scanner.calibration_value("two1nine"), Some(29));
+        assert_eq!(scanner.calibration_value("xtwone3four"), Some(24));
+        assert_eq!(scanner.calibration_value("zoneight234"), Some(14));
+        assert_eq!(scanner.calibration_value("oneight"), Some(18));
+        assert_eq!(scanner.calibration_value("twone"), Some(21));
+        assert_eq!(scanner.calibration_value("7pqrstsixteen"), Some(76));
+        assert_eq!(scanner.calibration_value("abcdef"), None);
+
+        assert_eq!(scanner.first("xtwone3"), Some(Match { start: 1, end: 4, value: 2 }));
+        assert_eq!(scanner.last("xtwone3x"), Some(Match { start: 6, end: 7, value: 3 }));
+    }
+
+    #[test]
+    fn configured_vocabulary() {
+        let teens = Scanner::new(&Vocabulary::digits().with_words(&ENGLISH).with_words(&ENGLISH_TEENS));
+        // The longest word starting first wins, and the one ending last from the other side
+        assert_eq!(teens.calibration_value("seventeenine"), Some(19));
+        assert_eq!(teens.calibration_value("xseventeen"), Some(17));
+        assert_eq!(teens.first("nineteen"), Some(Match { start: 0, end: 8, value: 19 }));
+
+        let german = Scanner::new(&Vocabulary::digits().parse("eins 1\nzwei 2\nfünf 5\n").unwrap());
+        // Offsets are in bytes, ü takes two
+        assert_eq!(german.last("zweiüfünfx"), Some(Match { start: 6, end: 11, value: 5 }));
+        assert_eq!(german.calibration_value("ééeinsüfünf"), Some(15));
+
+        assert!(Vocabulary::digits().parse("eins").is_err());
+        assert!(Vocabulary::digits().parse("eins one").is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_runner::Answer;
+
 #[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
 struct Point { row: i32, col: i32 }
 impl Point {
@@ -138,14 +140,14 @@ fn parse_input(input: &str) -> PipeMap {
     map
 }
 
-fn part_1() {
+fn part_1() -> Answer {
     let input = include_str!("input.txt");
     let map = parse_input(input);
     //println!("Map: {:#?}", map);
     let loop_count = map.find_loop().len();
     assert!(loop_count > 0);
     let max_dist = ((loop_count as f32) / 2.0).ceil() as i32;
-    println!("Part 1: {max_dist}");
+    max_dist.into()
 }
 
 /////////////////////////////
@@ -577,7 +579,7 @@ impl FloodMap {
     }
 }
 
-fn part_2() {
+fn part_2() -> Answer {
     let input = include_str!("input.txt");
     let mut map = FloodMap { chars: Vec::new() };
     map.initialize(input);
@@ -591,10 +593,12 @@ fn part_2() {
     
     //for line in &map.chars { println!("{:?}", line); }
     
-    println!("Num Inside: {num_inside}");
+    num_inside.into()
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    part_1();
-    part_2();
+    aoc_runner::answer!(1, part_1());
+    aoc_runner::answer!(2, part_2());
 }
@@ -1,5 +1,7 @@
 use std::mem::swap;
 
+use aoc_runner::Answer;
+
 const EXPANSION_CHAR : char = 'X';
 
 fn is_empty(chars: &Vec<char>) -> bool {
@@ -110,25 +112,44 @@ impl GalaxyMap {
     }
 }
 
-fn part_1() {
-    let input = include_str!("input.txt");
-    let map = GalaxyMap::parse(input, 2);
+// A size x size image with about one galaxy per row, spread so some rows and columns stay empty
+fn generate(size: usize) -> String {
+    let mut rng = aoc_runner::Rng::new(size as u64);
+    let mut image = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        for _ in 0..size {
+            image.push(if rng.chance(1, size) { '#' } else { '.' });
+        }
+        image.push('\n');
+    }
+    image
+}
+
+fn part_1(input: &str) -> Answer {
+    let map = aoc_runner::timed("parse", || GalaxyMap::parse(input, 2));
     //print_map(&map);
 
     let shortest_paths = map.get_sum_manhattan_distance();
-    println!("Part 1: {shortest_paths}");
+    shortest_paths.into()
 }
 
-fn part_2() {    
-    let input = include_str!("input.txt");
-    let map = GalaxyMap::parse(input, 1000000);
+fn part_2(input: &str) -> Answer {
+    let map = aoc_runner::timed("parse", || GalaxyMap::parse(input, 1000000));
     //print_map(&map);
 
     let shortest_paths = map.get_sum_manhattan_distance();
-    println!("Part 2: {shortest_paths}");
+    shortest_paths.into()
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    part_1();
-    part_2();
+    if let Some(size) = aoc_runner::generate_size() {
+        print!("{}", generate(size));
+        return;
+    }
+
+    let input = aoc_runner::read_input(include_str!("input.txt"));
+    aoc_runner::answer!(1, part_1(&input));
+    aoc_runner::answer!(2, part_2(&input));
 }
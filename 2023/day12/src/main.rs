@@ -1,4 +1,6 @@
-use std::{/*fs,*/ collections::{/*HashSet,*/ HashMap}};
+use std::{/*fs,*/ collections::{/*HashSet,*/ HashMap}, io::BufRead};
+
+use aoc_runner::Answer;
 
 const CHAR_BROKEN: char = '#';
 const CHAR_WORKING: char = '.';
@@ -28,6 +30,10 @@ fn parse_input(input: &str, duplicate_count: i64) -> SpringMap {
     map
 }
 
+fn parse_row(line: &str, duplicate_count: i64) -> SpringRow {
+    parse_input(line, duplicate_count).pop().expect("Expected a row")
+}
+
 #[allow(dead_code)]
 fn is_valid_permutation(perm: &str, row: &SpringRow) -> bool {
     if perm.len() != row.0.len() {
@@ -160,7 +166,24 @@ fn get_permutations(spring_row: &SpringRow/*, prefix: &str, out_perms: &mut Vec<
     num_permutations
 }
 
-fn part_1() {
+// Rows are independent, so they are solved as they are read and the cache only ever holds one row
+fn sum_permutations<R: BufRead>(reader: R, duplicate_count: i64) -> i64 {
+    let mut sum = 0;
+    let mut cache = HashMap::new();
+    let mut i = 0;
+    aoc_runner::for_each_line(reader, |line| {
+        let row = parse_row(line, duplicate_count);
+        let perm = get_permutations(&row, &mut cache);
+        cache.clear();
+
+        i += 1;
+        println!("[{i}]: found {perm} permutations!");
+        sum += perm;
+    });
+    sum
+}
+
+fn part_1() -> Answer {
     let input = include_str!("input.txt");
     let spring_map = parse_input(input, 0);
 
@@ -191,10 +214,10 @@ fn part_1() {
 
 //    fs::write("output.txt", all_perms.join("\n\n")).expect("msg");
 
-    println!("Part 1: {sum}");
+    sum.into()
 }
 
-fn part_2() {
+fn part_2() -> Answer {
     let input = include_str!("input.txt");
     let spring_map = parse_input(input, 4);
 
@@ -223,10 +246,18 @@ fn part_2() {
         //all_perms.push(perms.join("\n"));
     }
 
-    println!("Part 2: {sum}");
+    sum.into()
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    part_1();
-    part_2();
+    if let Some(path) = aoc_runner::input_path() {
+        aoc_runner::answer!(1, sum_permutations(aoc_runner::open_input(&path), 0));
+        aoc_runner::answer!(2, sum_permutations(aoc_runner::open_input(&path), 4));
+        return;
+    }
+
+    aoc_runner::answer!(1, part_1());
+    aoc_runner::answer!(2, part_2());
 }
@@ -1,9 +1,12 @@
 use std::collections::HashSet;
 
+use aoc_runner::{json, Answer, Explained, Value};
+
 const REFLECTION_VERT : bool = true;
 const REFLECTION_HORIZ : bool = false;
 
 type ReflectionPoint = (bool, usize, usize);
+type Reflection = (ReflectionPoint, usize, HashSet<(usize, usize)>);
 
 #[derive(Debug)]
 struct Pattern {
@@ -122,7 +125,7 @@ impl Pattern {
         }
     }
 
-    fn find_reflection(self: &Self, allowed_smudges: usize) -> (ReflectionPoint, usize) {
+    fn find_reflection(self: &Self, allowed_smudges: usize) -> Reflection {
         let mut max_reflect_horiz_len = 0;
         let mut max_reflect_horiz: (bool, usize, usize) = (REFLECTION_HORIZ, 0, 0);
         let mut all_smudges = HashSet::new();
@@ -210,9 +213,9 @@ impl Pattern {
         assert_ne!(max_reflect_horiz_len != 0, max_reflect_vert_len != 0);
 
         if max_reflect_horiz_len > 0 {
-            (max_reflect_horiz, max_reflect_horiz_len)
+            (max_reflect_horiz, max_reflect_horiz_len, all_smudges)
         } else {
-            (max_reflect_vert, max_reflect_vert_len)
+            (max_reflect_vert, max_reflect_vert_len, all_smudges)
         }
     }
 }
@@ -222,7 +225,7 @@ struct MirrorMap {
     patterns: Vec<Pattern>
 }
 impl MirrorMap {
-    fn find_reflections(self: &Self, allowed_smudges: usize) -> Vec<(ReflectionPoint, usize)> {
+    fn find_reflections(self: &Self, allowed_smudges: usize) -> Vec<Reflection> {
         self.patterns.iter()
             .map(|p| p.find_reflection(allowed_smudges))
             .collect()
@@ -246,29 +249,46 @@ fn parse_input(input: &str) -> MirrorMap {
     map
 }
 
-fn get_result(input: &str, allowed_smudges: usize) -> usize {
-    let map = parse_input(&input);
+fn get_result(input: &str, allowed_smudges: usize) -> Explained<Answer> {
+    let map = aoc_runner::timed("parse", || parse_input(&input));
     //println!("{:#?}", map);
     let reflection_points = map.find_reflections(allowed_smudges);
 
-    reflection_points.iter()
+    let sum : usize = reflection_points.iter()
         .map(|r| if r.0.0 == REFLECTION_VERT { r.0.1 + 1 } else { 100 * (r.0.1 + 1) } )
-        .sum()
+        .sum();
+
+    let reflections : Vec<Value> = reflection_points.iter().enumerate()
+        .map(|(pattern, (point, size, smudges))| {
+            let mut smudges : Vec<&(usize, usize)> = smudges.iter().collect();
+            smudges.sort();
+            json!({
+                "pattern": pattern,
+                "axis": if point.0 == REFLECTION_VERT { "vertical" } else { "horizontal" },
+                // Columns left of / rows above the mirror line
+                "before": point.1 + 1,
+                "size": size,
+                "smudges": smudges.iter().map(|(row, col)| format!("{row},{col}")).collect::<Vec<String>>().join(" "),
+            })
+        })
+        .collect();
+
+    Explained::new(sum.into()).with("reflections", &reflections)
 }
 
-fn part_1() {
+fn part_1() -> Explained<Answer> {
     let input = include_str!("input.txt");
-    let sum = get_result(input, 0);
-    println!("Part 1: {sum}");
+    get_result(input, 0)
 }
 
-fn part_2() {
+fn part_2() -> Explained<Answer> {
     let input = include_str!("input.txt");
-    let sum = get_result(input, 1);
-    println!("Part 2: {sum}");
+    get_result(input, 1)
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    part_1();
-    part_2();
+    aoc_runner::explain!(1, part_1());
+    aoc_runner::explain!(2, part_2());
 }
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use aoc_runner::Answer;
+
 type Point = (i32, i32);
 type Direction = (i32, i32);
 
@@ -160,16 +162,16 @@ fn parse_input(input: &str) -> Platform {
     platform
 }
 
-fn part_1()
+fn part_1() -> Answer
 {
     let input = include_str!("input.txt");
     let platform = parse_input(&input);
     let weight = platform.get_support_weight((0, -1));
 
-    println!("Part 1: {weight}");
+    weight.into()
 }
 
-fn part_2()
+fn part_2() -> Answer
 {
     let input = include_str!("input.txt");
     let mut platform = parse_input(&input);
@@ -221,10 +223,12 @@ fn part_2()
         }
     }
     
-    println!("Part 2: {weight}");
+    weight.into()
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    part_1();
-    part_2();
+    aoc_runner::answer!(1, part_1());
+    aoc_runner::answer!(2, part_2());
 }
@@ -1,6 +1,8 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, io::BufRead};
 
-type MirrorMap<'a> = HashMap<i32, Vec<(&'a str, i32)>>;
+use aoc_runner::Answer;
+
+type MirrorMap = HashMap<i32, Vec<(String, i32)>>;
 const OP_ASSIGN : char = '=';
 const OP_REMOVE : char = '-';
 
@@ -12,23 +14,23 @@ fn get_hash(input: &str) -> i32 {
         })
 }
 
-fn get_sequence_hash(input: &str) -> i32 {
-    input.split(',').into_iter()
-        .map(|x| get_hash(&x))
-        .sum()
+// Steps are comma separated on one (possibly huge) line, so they are streamed one at a time
+fn get_sequence_hash<R: BufRead>(reader: R) -> i64 {
+    let mut sum = 0;
+    aoc_runner::for_each_record(reader, b',', |x| sum += get_hash(&x) as i64);
+    sum
 }
 
-fn part_1() {
-    let input = include_str!("input.txt");
-    let hash_value = get_sequence_hash(&input);
+fn part_1<R: BufRead>(reader: R) -> Answer {
+    let hash_value = get_sequence_hash(reader);
 
-    println!("Part 1: {hash_value}");
+    hash_value.into()
 }
 
-fn get_part2_solution(input: &str) -> MirrorMap {
+fn get_part2_solution<R: BufRead>(reader: R) -> MirrorMap {
     let mut mirror_map : MirrorMap = MirrorMap::new();
 
-    for step in input.split(',').into_iter() {
+    aoc_runner::for_each_record(reader, b',', |step| {
         let mut step_chars = step.chars();
         let instruction_idx = step_chars.position(|x| x == OP_ASSIGN || x == OP_REMOVE).unwrap();
         let label = &step[0..instruction_idx];
@@ -46,11 +48,11 @@ fn get_part2_solution(input: &str) -> MirrorMap {
                 match existing_index {
                     Some(index) => { 
                         //println!("[Box {hash}] Replacing label {label} index {index} with value {value}");
-                        mirror_box[index] = (label, value); 
+                        mirror_box[index].1 = value; 
                     },
                     None => { 
                         //println!("[Box {hash}] Adding label {label} value {value}");
-                        mirror_box.push((label, value)); 
+                        mirror_box.push((label.to_string(), value)); 
                     }
                 }
             },
@@ -67,7 +69,7 @@ fn get_part2_solution(input: &str) -> MirrorMap {
         }
 
         //println!("{:?}", mirror_map);
-    }
+    });
 
     mirror_map
 }
@@ -80,14 +82,22 @@ fn get_mirror_map_power(mirror_map: &MirrorMap) -> i32 {
         }).flatten().sum()
 }
 
-fn part_2() {
-    let input = include_str!("input.txt");
-    let mirror_map = get_part2_solution(&input);
+fn part_2<R: BufRead>(reader: R) -> Answer {
+    let mirror_map = get_part2_solution(reader);
     let power = get_mirror_map_power(&mirror_map);
-    println!("Part 2: {power}");
+    power.into()
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    part_1();
-    part_2();
+    if let Some(path) = aoc_runner::input_path() {
+        aoc_runner::answer!(1, part_1(aoc_runner::open_input(&path)));
+        aoc_runner::answer!(2, part_2(aoc_runner::open_input(&path)));
+        return;
+    }
+
+    let input = include_str!("input.txt");
+    aoc_runner::answer!(1, part_1(input.as_bytes()));
+    aoc_runner::answer!(2, part_2(input.as_bytes()));
 }
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_runner::Answer;
 use once_cell::sync::Lazy;
 
 type Direction = (i32, i32);
@@ -87,7 +88,9 @@ impl GridMap {
     }
 
     #[allow(dead_code)]
-    fn print_route(self: &Self, energized_cells: &HashMap<Position, HashSet<Direction>>) {
+    // The beam's direction through each cell, or how many beams crossed it
+    fn render_route(self: &Self, energized_cells: &HashMap<Position, HashSet<Direction>>) -> String {
+        let mut out = String::new();
         for (row, line) in self.chars.iter().enumerate() {
             let s : String = line.iter().enumerate()
                 .map(|(col, char)| {
@@ -107,12 +110,14 @@ impl GridMap {
                         None => { *char }
                     }
                 }).collect();
-            println!("{s}");
+            out += &s;
+            out += "\n";
         }
+        out
     }
 
-    #[allow(dead_code)]
-    fn print_energized(self: &Self, energized_cells: &HashMap<Position, HashSet<Direction>>) {
+    fn render_energized(self: &Self, energized_cells: &HashMap<Position, HashSet<Direction>>) -> String {
+        let mut out = String::new();
         for (row, line) in self.chars.iter().enumerate() {
             let s : String = line.iter().enumerate()
                 .map(|(col, _)| {
@@ -121,8 +126,10 @@ impl GridMap {
                         None => { &CHAR_EMPTY }
                     }
                 }).collect();
-            println!("{s}");
+            out += &s;
+            out += "\n";
         }
+        out
     }
 
     fn get_char(self: &Self, pos: &Position) -> Option<char> {
@@ -205,14 +212,18 @@ fn parse_input(input: &str) -> GridMap {
     map
 }
 
-fn part_1() {
+fn part_1(render: bool) -> Answer {
     let input = include_str!("input.txt");
-    let map = parse_input(&input);
+    let map = aoc_runner::timed("parse", || parse_input(&input));
     
     //map.print();
 
     let energized_cells = map.calc_energized_cells(&(0,0), &DIRECTION_RIGHT);
     let num_energized_cells = energized_cells.len();
+
+    if render {
+        print!("{}", map.render_energized(&energized_cells));
+    }
     
     /*println!("");
     println!(" -------- ");
@@ -224,12 +235,12 @@ fn part_1() {
     println!(" -------- ");
     println!("");*/
 
-    println!("Part 1: {num_energized_cells}");
+    num_energized_cells.into()
 }
 
-fn part_2() {
+fn part_2() -> Answer {
     let input = include_str!("input.txt");
-    let map = parse_input(&input);
+    let map = aoc_runner::timed("parse", || parse_input(&input));
     
     //map.print();
 
@@ -262,10 +273,30 @@ fn part_2() {
     println!(" -------- ");
     println!("");*/
 
-    println!("Part 2: {}", best_all.len());
+    best_all.len().into()
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    part_1();
-    part_2();
+    if std::env::args().any(|a| a == "--render") {
+        part_1(true);
+        return;
+    }
+
+    aoc_runner::answer!(1, part_1(false));
+    aoc_runner::answer!(2, part_2());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_snapshot() {
+        let map = parse_input(".|..\\.\n......\n.-./..\n\\...|.\n");
+        let energized_cells = map.calc_energized_cells(&(0,0), &DIRECTION_RIGHT);
+        aoc_runner::assert_snapshot!("route", map.render_route(&energized_cells));
+        aoc_runner::assert_snapshot!("energized", map.render_energized(&energized_cells));
+    }
 }
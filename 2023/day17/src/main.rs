@@ -1,5 +1,7 @@
 use std::{collections::{HashMap, HashSet, BinaryHeap}, cmp::Ordering};
 
+use aoc_runner::Answer;
+
 type Direction = (i32, i32);
 type Point = (i32, i32);
 
@@ -107,7 +109,7 @@ impl LavaMap {
         }
     }
     
-    fn traverse_graph(self: &Self, start: &Point, end: &Point, min_same_dir: i32, max_same_dir: i32) -> u32 {
+    fn traverse_graph(self: &Self, start: &Point, end: &Point, min_same_dir: i32, max_same_dir: i32, print: bool) -> u32 {
         let mut open_set : BinaryHeap<OpenSetEntry> = BinaryHeap::new();
         let mut open_set_hash : HashSet<OpenSetEntry> = HashSet::new();
         let mut found_goal : Option<SearchCandidate> = None;
@@ -178,7 +180,9 @@ impl LavaMap {
 
        // println!("Path: {:?}", path);
     
-        //self.print_path(&path);
+        if print {
+            self.print_path(&path);
+        }
     
         let last_point = path.pop().unwrap(); // Remove the start point
         assert_eq!(&last_point.point, start);
@@ -187,7 +191,6 @@ impl LavaMap {
         total_cost
     }
 
-    #[allow(dead_code)]
     fn print_path(self: &Self, path: &Vec<&SearchCandidate>) {
         self.cells.iter().enumerate().for_each(|(j, row)| {
             let s : String = row.iter().enumerate().map(|(i, cell)| {
@@ -218,22 +221,28 @@ fn parse_input(input: &str) -> LavaMap {
     }
 }
 
-fn part_1() {
+fn part_1(render: bool) -> Answer {
     let input = include_str!("input.txt");
-    let grid = parse_input(&input);
-    let shortest_cost = grid.traverse_graph(&(0,0), &((grid.cells[0].len() as i32 - 1), grid.cells.len() as i32 - 1), 1, 3);
-
-    println!("Part 1: {shortest_cost}");
+    let grid = aoc_runner::timed("parse", || parse_input(&input));
+    let shortest_cost = grid.traverse_graph(&(0,0), &((grid.cells[0].len() as i32 - 1), grid.cells.len() as i32 - 1), 1, 3, render);
+    shortest_cost.into()
 }
 
-fn part_2() {
+fn part_2() -> Answer {
     let input = include_str!("input.txt");
-    let grid = parse_input(&input);
-    let shortest_cost = grid.traverse_graph(&(0,0), &((grid.cells[0].len() as i32 - 1), grid.cells.len() as i32 - 1), 4, 10);
+    let grid = aoc_runner::timed("parse", || parse_input(&input));
+    let shortest_cost = grid.traverse_graph(&(0,0), &((grid.cells[0].len() as i32 - 1), grid.cells.len() as i32 - 1), 4, 10, false);
 
-    println!("Part 2: {shortest_cost}");
+    shortest_cost.into()
 }
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    part_1();
-    part_2();
+    if std::env::args().any(|a| a == "--render") {
+        part_1(true);
+        return;
+    }
+
+    aoc_runner::answer!(1, part_1(false));
+    aoc_runner::answer!(2, part_2());
 }
@@ -1,3 +1,5 @@
+use aoc_runner::Answer;
+
 type Point = (i32, i32);
 
 fn hex_to_dec(hex: &[char]) -> u32 {
@@ -120,23 +122,25 @@ fn run_part(input: &str, color_is_instruction: bool) -> i64 {
     map.get_fill_size()
 }
 
-fn part_1()
+fn part_1() -> Answer
 {
     let input = include_str!("input.txt");
     let result = run_part(input, false);
     
-    println!("Part 1: {result}");
+    result.into()
 }
 
-fn part_2()
+fn part_2() -> Answer
 {
     let input = include_str!("input.txt");
     let result = run_part(input, true);
     
-    println!("Part 2: {result}");
+    result.into()
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    part_1();
-    part_2();
+    aoc_runner::answer!(1, part_1());
+    aoc_runner::answer!(2, part_2());
 }
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use aoc_runner::Answer;
+
 #[derive(PartialEq, Debug, Clone)]
 enum WorkflowResult {
     Workflow(String),
@@ -217,7 +219,7 @@ fn parse_input(input: &str) -> (Workflow, Vec<Part>) {
     (workflow, parts)
 }
 
-fn part_1() {
+fn part_1() -> Answer {
     let input = include_str!("input.txt");
     let (workflow, parts) = parse_input(input);
 
@@ -227,10 +229,10 @@ fn part_1() {
         .map(|p| p.get_total_rating())
         .sum();
     
-    println!("Part 1: {result}");
+    result.into()
 }
 
-fn part_2() {
+fn part_2() -> Answer {
     let input = include_str!("input.txt");
     let (workflow, _) = parse_input(input);
 
@@ -244,10 +246,12 @@ fn part_2() {
 
     let result = workflow.get_total_combinations(&part_limits);
 
-    println!("Part 2: {result}");
+    result.into()
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    part_1();
-    part_2();
+    aoc_runner::answer!(1, part_1());
+    aoc_runner::answer!(2, part_2());
 }
@@ -0,0 +1,154 @@
+use std::{collections::BTreeMap, fmt};
+
+// The colours part 2's power multiplies, whatever bag part 1 was played against
+pub const PUZZLE_COLOURS : [&str; 3] = ["red", "green", "blue"];
+
+// Cube counts by colour, for any colours the log mentions. Sorted so it prints the same every time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CubeSet {
+    counts: BTreeMap<String, u32>,
+}
+impl CubeSet {
+    // A comma separated list like `3 blue, 4 red`. A colour listed twice is added up.
+    pub fn parse(text: &str) -> Result<CubeSet, String> {
+        let mut set = CubeSet::default();
+        for entry in text.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+            let (count, colour) = entry.split_once(' ')
+                .ok_or_else(|| format!("expected 'count colour', got '{entry}'"))?;
+            let count : u32 = count.parse().map_err(|_| format!("'{count}' is not a cube count"))?;
+            *set.counts.entry(colour.trim().to_string()).or_insert(0) += count;
+        }
+        Ok(set)
+    }
+
+    pub fn get(&self, colour: &str) -> u32 {
+        self.counts.get(colour).copied().unwrap_or(0)
+    }
+
+    // The most of each colour in either set
+    pub fn max(&self, other: &CubeSet) -> CubeSet {
+        let mut result = self.clone();
+        for (colour, &count) in &other.counts {
+            let entry = result.counts.entry(colour.clone()).or_insert(0);
+            *entry = (*entry).max(count);
+        }
+        result
+    }
+
+    // Each colour there is more of than the limits allow, as (colour, count, limit).
+    // A colour the limits don't mention has a limit of 0.
+    pub fn exceeding(&self, limits: &CubeSet) -> Vec<(&str, u32, u32)> {
+        self.counts.iter()
+            .map(|(colour, &count)| (colour.as_str(), count, limits.get(colour)))
+            .filter(|(_, count, limit)| count > limit)
+            .collect()
+    }
+
+    pub fn fits_within(&self, limits: &CubeSet) -> bool {
+        self.exceeding(limits).is_empty()
+    }
+
+    // Product of the counts of the given colours, so a colour never seen makes it 0
+    pub fn power<'a>(&self, colours: impl Iterator<Item = &'a str>) -> u64 {
+        colours.map(|c| self.get(c) as u64).product()
+    }
+}
+impl fmt::Display for CubeSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries : Vec<String> = self.counts.iter().map(|(colour, count)| format!("{count} {colour}")).collect();
+        write!(f, "{}", entries.join(", "))
+    }
+}
+
+// A draw that could not have come from the bag, and which colours it had too many of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Infeasible<'a> {
+    pub draw: usize,
+    pub exceeding: Vec<(&'a str, u32, u32)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub draws: Vec<CubeSet>,
+}
+impl Game {
+    // `Game 1: 3 blue, 4 red; 1 red, 2 green`
+    pub fn parse(line: &str) -> Result<Game, String> {
+        let (name, draws) = line.split_once(':')
+            .ok_or_else(|| format!("line was not in proper format: {line}"))?;
+        let id = name.trim().strip_prefix("Game ")
+            .and_then(|id| id.trim().parse().ok())
+            .ok_or_else(|| format!("did not find a valid game ID in '{name}'"))?;
+        let draws = draws.split(';').map(CubeSet::parse).collect::<Result<_, _>>()?;
+        Ok(Game { id, draws })
+    }
+
+    // The fewest cubes of each colour the bag could have held
+    pub fn minimal_set(&self) -> CubeSet {
+        self.draws.iter().fold(CubeSet::default(), |acc, draw| acc.max(draw))
+    }
+
+    // The first draw showing more cubes than the limits allow, counting draws from 1
+    pub fn first_infeasible_draw<'a>(&'a self, limits: &CubeSet) -> Option<Infeasible<'a>> {
+        self.draws.iter().enumerate()
+            .map(|(i, draw)| Infeasible { draw: i + 1, exceeding: draw.exceeding(limits) })
+            .find(|infeasible| !infeasible.exceeding.is_empty())
+    }
+
+    pub fn is_feasible(&self, limits: &CubeSet) -> bool {
+        self.minimal_set().fits_within(limits)
+    }
+}
+
+// The smallest bag every one of the games could have been played with
+pub fn smallest_limits<'a>(games: impl IntoIterator<Item = &'a Game>) -> CubeSet {
+    games.into_iter().fold(CubeSet::default(), |acc, game| acc.max(&game.minimal_set()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE : &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn games() -> Vec<Game> {
+        SAMPLE.lines().map(|l| Game::parse(l).unwrap()).collect()
+    }
+
+    #[test]
+    fn sample_queries() {
+        let games = games();
+        let limits = CubeSet::parse("12 red, 13 green, 14 blue").unwrap();
+
+        let feasible : Vec<u32> = games.iter().filter(|g| g.is_feasible(&limits)).map(|g| g.id).collect();
+        assert_eq!(feasible, vec![1, 2, 5]);
+
+        let powers : Vec<u64> = games.iter().map(|g| g.minimal_set().power(PUZZLE_COLOURS.into_iter())).collect();
+        assert_eq!(powers, vec![48, 12, 1560, 630, 36]);
+
+        assert_eq!(games[2].first_infeasible_draw(&limits), Some(Infeasible { draw: 1, exceeding: vec![("red", 20, 12)] }));
+        assert_eq!(games[3].first_infeasible_draw(&limits), Some(Infeasible { draw: 3, exceeding: vec![("blue", 15, 14), ("red", 14, 12)] }));
+        assert_eq!(games[0].first_infeasible_draw(&limits), None);
+
+        assert_eq!(smallest_limits(&games[..2]).to_string(), "6 blue, 3 green, 4 red");
+        assert_eq!(smallest_limits(&games).to_string(), "15 blue, 13 green, 20 red");
+    }
+
+    #[test]
+    fn any_colours() {
+        let game = Game::parse("Game 7: 2 purple, 1 red; 5 purple, 1 purple").unwrap();
+        assert_eq!(game.minimal_set().to_string(), "6 purple, 1 red");
+        assert!(!game.is_feasible(&CubeSet::parse("12 red, 13 green, 14 blue").unwrap()));
+        assert!(game.is_feasible(&CubeSet::parse("6 purple, 1 red").unwrap()));
+        assert_eq!(game.minimal_set().power(["purple", "red", "teal"].into_iter()), 0);
+
+        assert!(Game::parse("Game x: 1 red").is_err());
+        assert!(CubeSet::parse("red 1").is_err());
+    }
+}
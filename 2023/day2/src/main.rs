@@ -1,120 +1,74 @@
-use std::cmp::max;
+mod games;
 
-struct DiceCounts
-{
-    red: i32,
-    green: i32,
-    blue: i32
-}
+use std::io::BufRead;
 
-impl DiceCounts {
-    fn new() -> DiceCounts {
-        DiceCounts { red: 0, green: 0, blue: 0 }
-    }
-}
+use aoc_runner::{json, Answer, Explained, Value};
+use games::{CubeSet, Game, PUZZLE_COLOURS};
 
-fn add_dice_counts(a: &DiceCounts, b: &DiceCounts) -> DiceCounts {
-    DiceCounts {
-        red: a.red + b.red,
-        green: a.green + b.green,
-        blue: a.blue + b.blue
-    }
+fn parse_game(line: &str) -> Game {
+    Game::parse(line).unwrap_or_else(|e| panic!("Invalid input: {e}"))
 }
 
-fn max_dice_counts(a: &DiceCounts, b: &DiceCounts) -> DiceCounts {
-    DiceCounts {
-        red: max(a.red, b.red),
-        green: max(a.green, b.green),
-        blue: max(a.blue, b.blue)
-    }
+fn get_result_1<R: BufRead>(reader: R, limits: &CubeSet) -> Explained<Answer> {
+    let mut result = 0;
+    let mut infeasible : Vec<Value> = Vec::new();
+    aoc_runner::for_each_line(reader, |x| {
+        let game = parse_game(x);
+        if game.is_feasible(limits) {
+            result += game.id;
+        } else if let Some(draw) = game.first_infeasible_draw(limits) {
+            infeasible.push(json!({
+                "game": game.id,
+                "draw": draw.draw,
+                "exceeding": draw.exceeding.iter()
+                    .map(|(colour, count, limit)| format!("{count} {colour} > {limit}"))
+                    .collect::<Vec<String>>(),
+            }));
+        }
+    });
+
+    Explained::new(result.into())
+        .with("limits", &limits.to_string())
+        .with("infeasible_games", &infeasible)
 }
 
-fn is_roll_result_valid(dice_limits: &DiceCounts, roll_counts: &DiceCounts) -> bool {
-    roll_counts.red <= dice_limits.red
-        && roll_counts.green <= dice_limits.green
-        && roll_counts.blue <= dice_limits.blue
+// The power only counts the puzzle's colours, so a game that never showed one of them has none
+fn get_result_2<R: BufRead>(reader: R) -> Answer {
+    let mut result : u64 = 0;
+    aoc_runner::for_each_line(reader, |x| result += parse_game(x).minimal_set().power(PUZZLE_COLOURS.into_iter()));
+    result.into()
 }
 
-fn parse_dice_count(line: &str) -> DiceCounts {
-    let mut counts = DiceCounts::new();
-
-    let dice_count: Vec<&str> = line.split(' ').collect();
-    match dice_count[1] {
-        "red" => counts.red += dice_count[0].parse::<i32>().expect("Invalid input"),
-        "green" => counts.green += dice_count[0].parse::<i32>().expect("Invalid input"),
-        "blue" => counts.blue += dice_count[0].parse::<i32>().expect("Invalid input"),
-        _ => {},
-    }
-
-    counts
+// The smallest bag that could have played the first `count` games
+fn get_smallest_limits<R: BufRead>(reader: R, count: usize) -> CubeSet {
+    let games : Vec<Game> = reader.lines()
+        .take(count)
+        .map(|x| parse_game(&x.expect("Failed to read input")))
+        .collect();
+    games::smallest_limits(&games)
 }
 
-fn parse_single_result(line: &str) -> DiceCounts {
-    line.split(',')
-        .map(|x| parse_dice_count(x.trim()))
-        .fold(DiceCounts::new(),
-              |acc, dice| add_dice_counts(&acc, &dice))
-}
-
-fn is_illegal_roll(line: &str, dice_limits: &DiceCounts) -> bool {
-    let result = parse_single_result(line);
-    let is_valid = is_roll_result_valid(dice_limits, &result);
-
-    !is_valid
-}
-
-fn get_id_if_valid(line: &str, dice_limits: &DiceCounts) -> i32 {
-    let (roll_id, roll_results) = line.split_once(':')
-        .expect("Line was not in proper format: {line}");
-
-    let roll_id = roll_id.split_whitespace()
-        .map(|x| x.parse::<i32>())
-        .filter(|x| x.is_ok())
-        .next().expect("Did not find a valid roll ID")
-        .expect("Did not find a valid roll ID");
-
-    let has_invalid_roll = roll_results.split(';')
-        .any(|x| is_illegal_roll(x.trim(), dice_limits));
-
-    if has_invalid_roll {
-        0
-    } else {
-        roll_id
-    }
-}
-
-fn get_result_1(input: &str, dice_limits: &DiceCounts) -> i32 {
-    let result = input.lines()
-        .map(|x| get_id_if_valid(x, &dice_limits))
-        .sum();
-    result
-}
-
-fn get_dice_power(line: &str) -> i32 {
-    let (_, roll_results) = line.split_once(':')
-        .expect("Line was not in proper format: {line}");
-
-    let max_dices = roll_results.split(';')
-        .map(|x| parse_single_result(x))
-        .fold(DiceCounts::new(),
-              |acc, dice| max_dice_counts(&acc, &dice));
-
-
-    max_dices.red * max_dices.blue * max_dices.green
-}
-
-fn get_result_2(input: &str) -> i32 {
-    input.lines()
-        .map(|x| get_dice_power(x))
-        .sum()
-}
+aoc_runner::install_counting_allocator!();
 
 fn main() {
+    // `--limits "12 red, 13 green, 14 blue"` plays part 1 against another bag
+    let limits = aoc_runner::arg_value("--limits").unwrap_or_else(|| String::from("12 red, 13 green, 14 blue"));
+    let limits = CubeSet::parse(&limits).unwrap_or_else(|e| panic!("Invalid --limits: {e}"));
+    let admit_first = aoc_runner::arg_value("--admit-first")
+        .map(|k| k.parse::<usize>().unwrap_or_else(|_| panic!("Invalid --admit-first '{k}'")));
+
     let input = include_str!("input.txt");
-    let dice_limits_1 = DiceCounts { red: 12, blue: 14, green: 13 };
-    let result_1 = get_result_1(input, &dice_limits_1);
-    println!("Result 1: {result_1}");
+    let open = || -> Box<dyn BufRead> {
+        match aoc_runner::input_path() {
+            Some(path) => Box::new(aoc_runner::open_input(&path)),
+            None => Box::new(input.as_bytes()),
+        }
+    };
+
+    if let Some(count) = admit_first {
+        println!("Smallest bag for the first {count} games: {}", get_smallest_limits(open(), count));
+    }
 
-    let result_2 = get_result_2(input);
-    println!("Result 2: {result_2}");
+    aoc_runner::explain!(1, get_result_1(open(), &limits));
+    aoc_runner::answer!(2, get_result_2(open()));
 }
@@ -1,25 +1,39 @@
-use std::{collections::{HashMap, VecDeque, HashSet}, fmt::Debug};
+use std::{collections::{HashSet, VecDeque}, fmt::Debug};
+use aoc_graph::{DotStyle, Graph, NodeId};
+use aoc_runner::Assumption;
 use num::integer::lcm;
 
+// Pulses from the button come from outside the module graph
+const BUTTON : NodeId = NodeId::MAX;
+
 #[derive(Debug)]
 struct PulseQueue {
-    queue: VecDeque<(String, String, PulseType)>,
+    queue: VecDeque<(NodeId, NodeId, PulseType)>,
+    // Module names by id, for the debug output
+    names: Vec<String>,
     debug: bool
 } 
 impl PulseQueue {
-    fn new(debug: bool) -> Self { PulseQueue { queue: VecDeque::new(), debug } }
+    fn new(map: &ComponentMap, debug: bool) -> Self {
+        let names = map.graph.nodes().map(|id| map.graph.label(id).clone()).collect();
+        PulseQueue { queue: VecDeque::new(), names, debug }
+    }
 
-    fn push(self: &mut Self, item: (String, String, PulseType)) {
-        if self.debug { println!("-Queueing {} -{}-> {}", item.0, if item.2 == PulseType::High { "high" } else { "low" }, item.1); }
+    fn name(self: &Self, id: NodeId) -> &str {
+        if id == BUTTON { "button" } else { &self.names[id as usize] }
+    }
+
+    fn push(self: &mut Self, item: (NodeId, NodeId, PulseType)) {
+        if self.debug { println!("-Queueing {} -{}-> {}", self.name(item.0), if item.2 == PulseType::High { "high" } else { "low" }, self.name(item.1)); }
         self.queue.push_back(item)
     }
 
-    fn pop(self: &mut Self) -> Option<(String, String, PulseType)> {
+    fn pop(self: &mut Self) -> Option<(NodeId, NodeId, PulseType)> {
         self.queue.pop_front()
     }
 }
 
-fn process_queue(queue: &mut PulseQueue, map: &mut ComponentMap, desired_pulse: Option<(&str, &str, PulseType)>) -> (i64, i64, bool) {
+fn process_queue(queue: &mut PulseQueue, map: &mut ComponentMap, desired_pulse: Option<(NodeId, NodeId, PulseType)>) -> (i64, i64, bool) {
     let mut num_high_pulses : i64 = 0;
     let mut num_low_pulses : i64 = 0;
     let mut received_desired = false;
@@ -32,19 +46,16 @@ fn process_queue(queue: &mut PulseQueue, map: &mut ComponentMap, desired_pulse:
                     PulseType::Low => { num_low_pulses += 1; },
                 }
 
-                if desired_pulse.is_some() {
-                    let pulse = desired_pulse.unwrap();
-                    if from == pulse.0 && to == pulse.1 && pulse_type == pulse.2 {
-                        received_desired = true;
-                    }
+                if desired_pulse == Some((from, to, pulse_type)) {
+                    received_desired = true;
                 }
 
-                if queue.debug { println!("+Processing {} -{}-> {}", from, if pulse_type == PulseType::High { "high" } else { "low" }, to); }
+                if queue.debug { println!("+Processing {} -{}-> {}", queue.name(from), if pulse_type == PulseType::High { "high" } else { "low" }, queue.name(to)); }
         
-                match map.try_get_module(&to) {
+                match map.try_get_module(to) {
                     Some(module) => { 
-                        module.receive_pulse(&from, &pulse_type, queue); 
-                        if queue.debug { println!("{to} now {module:?}"); }
+                        module.receive_pulse(from, &pulse_type, queue); 
+                        if queue.debug { println!("{} now {module:?}", queue.name(to)); }
                     },
                     None => { /* Consumed */}
                 }
@@ -64,53 +75,54 @@ enum PulseType {
 }
 
 trait PowerModule : Debug {
-    fn connect_inputs(self: &mut Self, _inputs: &Vec<String>) {}
+    fn connect_inputs(self: &mut Self, _inputs: &[NodeId]) {}
     fn as_str(self: &Self) -> String { String::new() }
 
-    fn receive_pulse(self: &mut Self, _from: &String, _pulse_type: &PulseType, _output_queue: &mut PulseQueue) { panic!("Unimplemented"); }
-    fn get_outputs(self: &Self) -> &Vec<String> { panic!("Unimplemented"); }
+    fn receive_pulse(self: &mut Self, _from: NodeId, _pulse_type: &PulseType, _output_queue: &mut PulseQueue) { panic!("Unimplemented"); }
     fn reset(self: &mut Self) {}
+    fn dot_style(self: &Self) -> DotStyle { DotStyle::default() }
+    fn is_conjunction(self: &Self) -> bool { false }
 }
 
 #[derive(Debug)]
 struct Broadcast {
-    name: String,
-    outputs: Vec<String>,
+    id: NodeId,
+    outputs: Vec<NodeId>,
 }
 impl Broadcast {
-    fn new(name: &str, outputs: &Vec<String>) -> Self {
-        Broadcast { name: name.to_string(), outputs: outputs.clone() }
+    fn new(id: NodeId, outputs: &Vec<NodeId>) -> Self {
+        Broadcast { id, outputs: outputs.clone() }
     }
 }
 impl PowerModule for Broadcast {
-    fn get_outputs(self: &Self) -> &Vec<String> { &self.outputs }
+    fn dot_style(self: &Self) -> DotStyle { DotStyle::new("doublecircle", "palegreen") }
 
-    fn receive_pulse(self: &mut Self, _from: &String, pulse_type: &PulseType, output_queue: &mut PulseQueue) {
+    fn receive_pulse(self: &mut Self, _from: NodeId, pulse_type: &PulseType, output_queue: &mut PulseQueue) {
         for output in &self.outputs {
-            output_queue.push((self.name.clone(), output.clone(), *pulse_type));
+            output_queue.push((self.id, *output, *pulse_type));
         }
     }
 }
 
 #[derive(Debug)]
 struct FlipFlop {
-    name: String,
+    id: NodeId,
     is_on: bool,
-    outputs: Vec<String>,
+    outputs: Vec<NodeId>,
 }
 impl FlipFlop {
-    fn new(name: String, outputs: &Vec<String>) -> Self {
-        FlipFlop { name, is_on: false, outputs: outputs.clone() }
+    fn new(id: NodeId, outputs: &Vec<NodeId>) -> Self {
+        FlipFlop { id, is_on: false, outputs: outputs.clone() }
     }
 }
 impl PowerModule for FlipFlop {
-    fn get_outputs(self: &Self) -> &Vec<String> { &self.outputs }
+    fn dot_style(self: &Self) -> DotStyle { DotStyle::new("box", "lightblue") }
 
     fn as_str(self: &Self) -> String { 
         if self.is_on { String::from("on") } else { String::from("off") }
     }
     
-    fn receive_pulse(self: &mut Self, _from: &String, pulse_type: &PulseType, output_queue: &mut PulseQueue) {
+    fn receive_pulse(self: &mut Self, _from: NodeId, pulse_type: &PulseType, output_queue: &mut PulseQueue) {
         match pulse_type {
             PulseType::Low => {
                 self.is_on = !self.is_on;
@@ -118,7 +130,7 @@ impl PowerModule for FlipFlop {
                 let out_type = if self.is_on { PulseType::High } else { PulseType::Low };
 
                 for output in &self.outputs {
-                    output_queue.push((self.name.clone(), output.clone(), out_type));
+                    output_queue.push((self.id, *output, out_type));
                 }
             },
             _ => {}
@@ -132,22 +144,23 @@ impl PowerModule for FlipFlop {
 
 #[derive(Debug)]
 struct Conjunction {
-    name: String,
-    input_states: HashMap<String, PulseType>,
-    outputs: Vec<String>,
+    id: NodeId,
+    input_states: Vec<(NodeId, PulseType)>,
+    outputs: Vec<NodeId>,
 }
 impl Conjunction {
-    fn new(name: String, outputs: &Vec<String>) -> Conjunction {
-        Conjunction { name, input_states: HashMap::new(), outputs: outputs.clone() }
+    fn new(id: NodeId, outputs: &Vec<NodeId>) -> Conjunction {
+        Conjunction { id, input_states: Vec::new(), outputs: outputs.clone() }
     }
 }
 impl PowerModule for Conjunction {
-    fn get_outputs(self: &Self) -> &Vec<String> { &self.outputs }
+    fn dot_style(self: &Self) -> DotStyle { DotStyle::new("diamond", "orange") }
+    fn is_conjunction(self: &Self) -> bool { true }
 
     fn as_str(self: &Self) -> String { 
         let mut s = String::from("-");
         for (k, v) in &self.input_states {
-            s += k;
+            s += &k.to_string();
             s += "|";
             s += match v {
                 PulseType::High => "hi",
@@ -158,14 +171,15 @@ impl PowerModule for Conjunction {
         s
     }
 
-    fn connect_inputs(self: &mut Self, inputs: &Vec<String>) {
+    fn connect_inputs(self: &mut Self, inputs: &[NodeId]) {
         for input in inputs {
-            self.input_states.insert(input.clone(), PulseType::Low);
+            self.input_states.push((*input, PulseType::Low));
         }
     }
 
-    fn receive_pulse(self: &mut Self, from: &String, pulse_type: &PulseType, output_queue: &mut PulseQueue) {
-        self.input_states.insert(from.clone(), *pulse_type);
+    fn receive_pulse(self: &mut Self, from: NodeId, pulse_type: &PulseType, output_queue: &mut PulseQueue) {
+        let state = self.input_states.iter_mut().find(|(input, _)| *input == from).expect("Pulse from a module that isn't an input");
+        state.1 = *pulse_type;
 
         let pulse_type = match self.input_states.iter().all(|(_, v)| v == &PulseType::High) {
             true => PulseType::Low,
@@ -173,7 +187,7 @@ impl PowerModule for Conjunction {
         };
 
         for output in &self.outputs {
-            output_queue.push((self.name.clone(), output.clone(), pulse_type));
+            output_queue.push((self.id, *output, pulse_type));
         }
     }
 
@@ -186,106 +200,163 @@ impl PowerModule for Conjunction {
 
 #[derive(Debug)]
 struct ComponentMap {
-    modules: HashMap<String, Box<dyn PowerModule>>,
-    buckets: Vec<HashSet<String>>,
-    inputs : HashMap<String, Vec<String>>
+    // Every module, and everything they send pulses to, by name. Edges keep each module's output order.
+    graph: Graph<String>,
+    // By node id; None for names only ever sent pulses (e.g. rx)
+    modules: Vec<Option<Box<dyn PowerModule>>>,
+    buckets: Vec<HashSet<NodeId>>,
+    // What sends pulses to each node, by node id
+    inputs : Vec<Vec<NodeId>>,
+    broadcaster: NodeId,
 }
 impl ComponentMap {
     fn to_string(self: &Self) -> String {
-        self.modules.iter()
-            .map(|(k, v)| {
-                k.clone() + "_" + &v.as_ref().as_str() + ";"
-            })
+        self.modules.iter().enumerate()
+            .filter_map(|(id, module)| module.as_ref().map(|m| id.to_string() + "_" + &m.as_str() + ";"))
             .collect()
     }
 
     fn from_input(input: &str) -> Self {
-        let mut map = ComponentMap { modules: HashMap::new(), buckets: Vec::new(), inputs: HashMap::new() };
-        let mut inputs: HashMap<String, Vec<String>> = HashMap::new();
+        let mut graph : Graph<String> = Graph::new_directed();
+        let mut modules : Vec<(NodeId, char, Vec<NodeId>)> = Vec::new();
 
         for line in input.lines() {
             let parts : Vec<&str> = line.split(" -> ").collect();
-            let outputs : Vec<String> = parts[1].split(", ").map(|s| s.to_string()).collect();
-            let mut part_name = parts[0].split_at(1).1;
-
-            match parts[0].chars().nth(0).unwrap() {
-                '%' => {
-                    map.modules.insert(part_name.to_string(), Box::new(FlipFlop::new(part_name.to_string(), &outputs)));
-                },
-                '&' => {
-                    map.modules.insert(part_name.to_string(), Box::new(Conjunction::new(part_name.to_string(), &outputs)));
-                },
-                _ => {
-                    assert_eq!(parts[0], "broadcaster");
-                    part_name = parts[0];
-                    map.modules.insert(parts[0].to_string(), Box::new(Broadcast::new(parts[0], &outputs)));
-                }
-            }
-
+            let kind = parts[0].chars().nth(0).unwrap();
+            let part_name = match kind {
+                '%' | '&' => parts[0].split_at(1).1,
+                _ => { assert_eq!(parts[0], "broadcaster"); parts[0] }
+            };
+
+            let id = graph.add_node(part_name.to_string());
+            let outputs : Vec<NodeId> = parts[1].split(", ").map(|s| graph.add_node(s.to_string())).collect();
             for output in &outputs {
-                inputs.entry(output.clone()).or_insert(Vec::new()).push(part_name.to_string());
+                graph.add_edge(id, *output, ());
             }
+            modules.push((id, kind, outputs));
         }
 
-        for (k, v) in &inputs {
-            match map.try_get_module(k) {
-                Some(module) => { module.connect_inputs(v); },
-                None => {}
-            }
+        let mut inputs : Vec<Vec<NodeId>> = vec![Vec::new(); graph.node_count()];
+        for (from, to, _) in graph.all_edges() {
+            inputs[to as usize].push(from);
         }
 
-        map.inputs = inputs;
-
-        for output in map.modules.get("broadcaster").unwrap().get_outputs() {
-            let mut new_set = HashSet::new();
-            new_set.insert(output.clone());
-
-            map.buckets.push(new_set);
+        let broadcaster = graph.id(&String::from("broadcaster")).expect("No broadcaster");
+        let mut map = ComponentMap { modules: (0..graph.node_count()).map(|_| None).collect(), graph, buckets: Vec::new(), inputs, broadcaster };
+        for (id, kind, outputs) in modules {
+            let mut module : Box<dyn PowerModule> = match kind {
+                '%' => Box::new(FlipFlop::new(id, &outputs)),
+                '&' => Box::new(Conjunction::new(id, &outputs)),
+                _ => Box::new(Broadcast::new(id, &outputs)),
+            };
+            module.connect_inputs(&map.inputs[id as usize]);
+            map.modules[id as usize] = Some(module);
         }
 
-        let length = map.buckets.len();
-        for i in 0..length {
-            loop {
-                let mut added_something = false;
-
-                for node in map.buckets[i].clone() {
-                    if node == "xn" { continue; }
-
-                    match map.modules.get(&node) {
-                        Some(module) => {
-                            for output in module.get_outputs() {
-                                if map.buckets[i].insert(output.clone()) {
-                                    added_something = true;
-                                }
-                            }              
-                        },
-                        None => {}
+        let xn = map.id("xn");
+        for output in map.graph.neighbors(broadcaster) {
+            let mut bucket = HashSet::new();
+            let mut queue = vec![output];
+            bucket.insert(output);
+            while let Some(node) = queue.pop() {
+                if Some(node) == xn { continue; }
+
+                for next in map.graph.neighbors(node) {
+                    if bucket.insert(next) {
+                        queue.push(next);
                     }
                 }
-
-                if !added_something {
-                    break;
-                }
             }
+            map.buckets.push(bucket);
         }
 
         map
     }
 
-    fn try_get_module(self: &mut Self, module_name: &str) -> Option<&mut Box<dyn PowerModule>> {
-        self.modules.get_mut(module_name)
+    fn id(self: &Self, name: &str) -> Option<NodeId> {
+        self.graph.id(&name.to_string())
+    }
+
+    fn name(self: &Self, id: NodeId) -> &str {
+        self.graph.label(id)
+    }
+
+    // Anything without a module (e.g. rx) is a sink
+    fn to_dot(self: &Self) -> String {
+        self.graph.to_dot("day20", |name| {
+            match self.id(name).and_then(|id| self.modules[id as usize].as_ref()) {
+                Some(module) => module.dot_style(),
+                None => DotStyle::new("doubleoctagon", "salmon"),
+            }
+        }, |_| None)
+    }
+
+    fn try_get_module(self: &mut Self, id: NodeId) -> Option<&mut Box<dyn PowerModule>> {
+        self.modules.get_mut(id as usize)?.as_mut()
     }
 
     fn reset(self: &mut Self) {
-        for (_, v) in &mut self.modules {
-            v.reset();
+        for module in self.modules.iter_mut().flatten() {
+            module.reset();
         }
     }
 }
 
+// Part 2 counts presses until each input of xn sends it a high pulse and takes the LCM, which is
+// only right when rx hangs off a single conjunction whose inputs are separate counters
+const PART_2_ASSUMPTIONS : &[Assumption<ComponentMap>] = &[
+    Assumption { name: "rx is fed only by the conjunction xn", check: check_rx_feed },
+    Assumption { name: "xn has four inputs, each at the end of its own independent sub-counter", check: check_sub_counters },
+];
+
+fn check_rx_feed(map: &ComponentMap) -> Result<(), String> {
+    let Some(rx) = map.id("rx") else {
+        return Err(String::from("nothing sends pulses to rx"));
+    };
+    let inputs : Vec<&str> = map.inputs[rx as usize].iter().map(|&id| map.name(id)).collect();
+    if inputs != ["xn"] {
+        return Err(format!("rx is fed by {}", inputs.join(", ")));
+    }
+    match map.id("xn").and_then(|xn| map.modules.get(xn as usize)?.as_ref()) {
+        Some(module) if module.is_conjunction() => Ok(()),
+        Some(_) => Err(String::from("xn is not a conjunction")),
+        None => Err(String::from("xn is not a module")),
+    }
+}
+
+// Each bucket is everything reachable from one broadcaster output without passing through xn
+fn check_sub_counters(map: &ComponentMap) -> Result<(), String> {
+    let Some(xn) = map.id("xn") else {
+        return Err(String::from("nothing sends pulses to xn"));
+    };
+    let xn_inputs = &map.inputs[xn as usize];
+    if xn_inputs.len() != 4 || map.buckets.len() != 4 {
+        return Err(format!("xn has {} inputs and the broadcaster starts {} sub-counters", xn_inputs.len(), map.buckets.len()));
+    }
+
+    for (i, bucket) in map.buckets.iter().enumerate() {
+        let feeding = xn_inputs.iter().filter(|input| bucket.contains(*input)).count();
+        if feeding != 1 {
+            return Err(format!("the sub-counter starting at {} reaches {feeding} of xn's inputs",
+                map.name(map.graph.edges(map.broadcaster)[i].0)));
+        }
+
+        for other in &map.buckets[i + 1..] {
+            if let Some(shared) = bucket.intersection(other).find(|&&m| map.name(m) != "xn" && map.name(m) != "rx") {
+                return Err(format!("{} is part of more than one sub-counter", map.name(*shared)));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn get_graph_dot(input: &str) -> String {
+    ComponentMap::from_input(input).to_dot()
+}
+
 pub fn get_result_part1(input: &str, button_presses: usize, debug: bool) -> i64 {
-    let mut map = ComponentMap::from_input(input);
-    let mut queue = PulseQueue::new(debug);
+    let mut map = aoc_runner::timed("parse", || ComponentMap::from_input(input));
+    let mut queue = PulseQueue::new(&map, debug);
 
     let mut remembered_states : Vec<String> = Vec::new();
     remembered_states.push(map.to_string());
@@ -299,7 +370,7 @@ pub fn get_result_part1(input: &str, button_presses: usize, debug: bool) -> i64
     for i in 0..button_presses {
         press_count += 1;
         
-        queue.push((String::from("button"), String::from("broadcaster"), PulseType::Low));        
+        queue.push((BUTTON, map.broadcaster, PulseType::Low));        
         let result = process_queue(&mut queue, &mut map,  None);
         results.push((result.0, result.1));
 
@@ -344,13 +415,18 @@ pub fn get_result_part1(input: &str, button_presses: usize, debug: bool) -> i64
     total_low * total_hi
 }
 
-pub fn get_result_part2(input: &str, debug: bool) -> i64 {
-    let mut map = ComponentMap::from_input(input);
-    let mut queue = PulseQueue::new(debug);
+// None when the input breaks one of the assumptions part 2 relies on, after printing which
+pub fn get_result_part2(input: &str, debug: bool) -> Option<i64> {
+    let mut map = aoc_runner::timed("parse", || ComponentMap::from_input(input));
+    if !aoc_runner::check_assumptions(2, &map, PART_2_ASSUMPTIONS) {
+        return None;
+    }
+    let mut queue = PulseQueue::new(&map, debug);
 
     if debug { println!("{map:#?}"); }
 
-    let desired_inputs = map.inputs.get("xn").unwrap().clone();
+    let xn = map.id("xn").unwrap();
+    let desired_inputs = map.inputs[xn as usize].clone();
     let mut loop_counts : Vec<i64> = Vec::new();
 
     for input in &desired_inputs {
@@ -360,10 +436,10 @@ pub fn get_result_part2(input: &str, debug: bool) -> i64 {
         loop {
             press_count += 1;
     
-            queue.push((String::from("button"), String::from("broadcaster"), PulseType::Low));        
-            let result = process_queue(&mut queue, &mut map, Some((input, "xn", PulseType::High)));
+            queue.push((BUTTON, map.broadcaster, PulseType::Low));        
+            let result = process_queue(&mut queue, &mut map, Some((*input, xn, PulseType::High)));
             if result.2 {
-                println!("rx recieved HIGH from {input} after {press_count} presses!");
+                println!("rx recieved HIGH from {} after {press_count} presses!", map.name(*input));
                 loop_counts.push(press_count);
                 break;
             }
@@ -385,7 +461,6 @@ pub fn get_result_part2(input: &str, debug: bool) -> i64 {
 
     loop_counts.into_iter()
         .reduce(|a, b| lcm(a, b))
-        .unwrap()
 }
 
 #[cfg(test)]
@@ -413,6 +488,16 @@ mod tests {
     #[test]
     fn part_2_input() {
         let result = get_result_part2(include_str!("input.txt"), false);
-        assert_eq!(result, 228060006554227);
+        assert_eq!(result, Some(228060006554227));
+    }
+
+    #[test]
+    fn part_2_assumptions() {
+        // Neither sample has an rx to count towards
+        let map = ComponentMap::from_input(include_str!("sample_2.txt"));
+        assert_eq!(aoc_runner::failed_assumptions(&map, PART_2_ASSUMPTIONS).len(), 2);
+
+        let map = ComponentMap::from_input("broadcaster -> a\n%a -> xn\n%xn -> rx");
+        assert_eq!(check_rx_feed(&map), Err(String::from("xn is not a conjunction")));
     }
 }
\ No newline at end of file
@@ -1,18 +1,29 @@
 mod day20;
 
-fn part_1() {
+use aoc_runner::Answer;
+
+fn part_1() -> Answer {
     let input = include_str!("input.txt");
     let result = day20::get_result_part1(input, 1000, false);    
-    println!("Part 1: {result}");
+    result.into()
 }
 
-fn part_2() {
+fn part_2() -> Option<Answer> {
     let input = include_str!("input.txt");
-    let result = day20::get_result_part2(input, false);
-    println!("Part 2: {result}");
+    let result = day20::get_result_part2(input, false)?;
+    Some(result.into())
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
-    part_1();
-    part_2();
+    if std::env::args().any(|a| a == "--graph") {
+        print!("{}", day20::get_graph_dot(include_str!("input.txt")));
+        return;
+    }
+
+    aoc_runner::answer!(1, part_1());
+    if let Some(answer) = part_2() {
+        aoc_runner::answer!(2, answer);
+    }
 }
@@ -1,5 +1,7 @@
 use std::collections::{HashSet, HashMap};
 
+use aoc_runner::Assumption;
+
 const CHAR_ROCK : char = '#';
 const CHAR_EMPTY : char = '.';
 const CHAR_STEP : char = 'O';
@@ -187,7 +189,9 @@ impl InfiniteGarden {
     }
 
 
-    fn print(self: &mut Self, step_count: i64, steps: &HashMap<Point, HashSet<Point>>, equilibrium: &Equilibrium) {
+    // Every garden copy reached so far side by side, gardens at equilibrium showing their settled steps
+    fn render(self: &mut Self, step_count: i64, steps: &HashMap<Point, HashSet<Point>>, equilibrium: &Equilibrium) -> String {
+        let mut out = String::new();
         let min_x = self.gardens.iter().map(|(k, _)| k.x).min().unwrap();
         let min_y = self.gardens.iter().map(|(k, _)| k.y).min().unwrap();
         let max_x = self.gardens.iter().map(|(k, _)| k.x).max().unwrap();
@@ -221,11 +225,13 @@ impl InfiniteGarden {
                     s += " ";
                 }
 
-                println!("{s}");
+                out += &s;
+                out += "\n";
             }
 
-            println!("");
+            out += "\n";
         }
+        out
     }
 }
 
@@ -300,11 +306,13 @@ impl Garden {
         s
     }
 
-    fn print(self: &Self, steps: &HashSet<Point>) {
+    fn render(self: &Self, steps: &HashSet<Point>) -> String {
+        let mut out = String::new();
         for row in 0..self.dim.y {
-            let s = self.get_row_as_str(row, steps);
-            println!("{s}");
+            out += &self.get_row_as_str(row, steps);
+            out += "\n";
         }
+        out
     }
 
     fn has_rock(self: &Self, point: &Point) -> bool {
@@ -365,7 +373,7 @@ impl Garden {
 }
 
 pub fn get_result_part1(input: &str, step_count: i64, debug: bool) -> usize {
-    let mut garden = Garden::from_input(input);
+    let mut garden = aoc_runner::timed("parse", || Garden::from_input(input));
     garden.reached_start = true;
     
     let mut steps = HashSet::new();
@@ -373,7 +381,7 @@ pub fn get_result_part1(input: &str, step_count: i64, debug: bool) -> usize {
     
     if debug {
         println!("Starting Layout:");
-        garden.print(&steps);
+        print!("{}", garden.render(&steps));
         println!("\n------------\n");
     }
 
@@ -384,7 +392,7 @@ pub fn get_result_part1(input: &str, step_count: i64, debug: bool) -> usize {
 
         if debug {
             println!("After {} step(s):\n", i+1);
-            garden.print(&steps);
+            print!("{}", garden.render(&steps));
             println!("\n------------\n");
         }
     }
@@ -418,7 +426,32 @@ fn simplified_lagrange(values: &(i64, i64, i64)) -> (i64, i64, i64) {
     )
   }
 
-pub fn solve_part2(input: &str, step_count: i64, debug: bool) -> i64 {
+// Part 2 fits a quadratic through the counts after whole numbers of gardens, which only grows
+// quadratically when the walk reaches every neighbouring garden at the same time
+const PART_2_ASSUMPTIONS : &[Assumption<Garden>] = &[
+    Assumption { name: "the garden is square", check: |garden| {
+        if garden.dim.x == garden.dim.y { Ok(()) }
+        else { Err(format!("it is {}x{}", garden.dim.x, garden.dim.y)) }
+    } },
+    Assumption { name: "the start is in the centre", check: |garden| {
+        let centre = Point { x: garden.dim.x / 2, y: garden.dim.y / 2 };
+        if garden.start_point == centre { Ok(()) }
+        else { Err(format!("it is at {},{} rather than {},{}", garden.start_point.x, garden.start_point.y, centre.x, centre.y)) }
+    } },
+    Assumption { name: "the start row and column are open", check: |garden| {
+        let start = garden.start_point;
+        match garden.rocks.iter().filter(|r| r.x == start.x || r.y == start.y).min_by_key(|r| (r.y, r.x)) {
+            Some(rock) => Err(format!("there is a rock at {},{}", rock.x, rock.y)),
+            None => Ok(()),
+        }
+    } },
+];
+
+// None when the input breaks one of the assumptions the interpolation relies on, after printing which
+pub fn solve_part2(input: &str, step_count: i64, debug: bool) -> Option<i64> {
+    if !aoc_runner::check_assumptions(2, &Garden::from_input(input), PART_2_ASSUMPTIONS) {
+        return None;
+    }
     let input_dim = input.lines().into_iter().count() as i64;
     
     let values = (
@@ -429,11 +462,11 @@ pub fn solve_part2(input: &str, step_count: i64, debug: bool) -> i64 {
 
     let poly = simplified_lagrange(&values);
     let target = (step_count - input_dim/2) / input_dim;
-    poly.0 * target * target + poly.1 * target + poly.2
+    Some(poly.0 * target * target + poly.1 * target + poly.2)
   }
 
 pub fn get_result_part2(input: &str, step_count: i64, debug: bool) -> usize {
-    let mut garden = InfiniteGarden::from_input(input);
+    let mut garden = aoc_runner::timed("parse", || InfiniteGarden::from_input(input));
     
     let mut starting_steps = HashSet::new();
     starting_steps.insert(garden.default_garden.start_point);
@@ -445,7 +478,7 @@ pub fn get_result_part2(input: &str, step_count: i64, debug: bool) -> usize {
     
     if debug {
         println!("Starting Layout:");
-        garden.print(0, &step_map, &equilibrium);
+        print!("{}", garden.render(0, &step_map, &equilibrium));
         println!("------------\n");
     }
 
@@ -455,7 +488,7 @@ pub fn get_result_part2(input: &str, step_count: i64, debug: bool) -> usize {
 
         if debug {
             println!("After {} step(s), {} at equilibrium:", i+1, equilibrium.gardens.len());
-            garden.print(i, &step_map, &equilibrium);
+            print!("{}", garden.render(i, &step_map, &equilibrium));
             println!("------------\n");
         }
     }
@@ -481,9 +514,16 @@ mod tests {
 
     #[test]
     fn part_2_sample_1() {
-        let result = solve_part2(include_str!("sample.txt"), 6, true);
+        let result = get_result_part2(include_str!("sample.txt"), 6, true);
         assert_eq!(result, 16);
     }
+
+    #[test]
+    fn part_2_sample_assumptions() {
+        // The sample has rocks in the start row, so it can't be solved by interpolating
+        let result = solve_part2(include_str!("sample.txt"), 6, true);
+        assert_eq!(result, None);
+    }
         
     #[test]
     fn part_2_sample_2() {
@@ -518,6 +558,26 @@ mod tests {
     #[test]
     fn part_2_input() {
         let result = solve_part2(include_str!("input.txt"), 26501365, false);
-        assert_eq!(result, 636391426712747);
+        assert_eq!(result, Some(636391426712747));
+    }
+
+    #[test]
+    fn render_snapshot() {
+        let input = ".....\n.#.#.\n..S..\n.#...\n.....\n";
+
+        let mut garden = Garden::from_input(input);
+        let mut steps = HashSet::from([garden.start_point]);
+        for _ in 0..2 {
+            steps = garden.run_steps(&steps).0;
+        }
+        aoc_runner::assert_snapshot!("garden", garden.render(&steps));
+
+        let mut garden = InfiniteGarden::from_input(input);
+        let mut equilibrium = Equilibrium { steps: (HashSet::new(), HashSet::new()), gardens: HashMap::new() };
+        let mut step_map = HashMap::from([(Point::zero(), HashSet::from([garden.default_garden.start_point]))]);
+        for i in 0..4 {
+            garden.run_steps(i, &mut step_map, &mut equilibrium);
+        }
+        aoc_runner::assert_snapshot!("infinite_garden", garden.render(3, &step_map, &equilibrium));
     }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use crate::types::BrickTower;
 
-use std::{iter, cmp::max};
+use std::{iter, cmp::max, fmt::Write};
 
 fn print_vert_label(tower: &BrickTower, is_xz: bool) -> (String, String) {
     let max_horiz = if is_xz { tower.bounds.1.x } else { tower.bounds.1.y };
@@ -28,7 +28,7 @@ fn print_vert_label(tower: &BrickTower, is_xz: bool) -> (String, String) {
         label)
 }
 
-fn print_vert_labels(tower: &BrickTower) {
+fn render_vert_labels(tower: &BrickTower, out: &mut String) {
     let label_x = print_vert_label(tower, true);
     let label_y = print_vert_label(tower, false);
     
@@ -38,27 +38,29 @@ fn print_vert_labels(tower: &BrickTower) {
         + tower.bounds.1.z.to_string().len()
         + 1; // Padding
 
-    println!("{}{}{}{}{}{}{}",
+    writeln!(out, "{}{}{}{}{}{}{}",
         label_x.0,
         iter::repeat(" ").take(max_x_chars - label_x.0.len()).collect::<String>(),
         label_y.0,
         iter::repeat(" ").take(max_x_chars - label_y.0.len()).collect::<String>(),
         label_x.0,
         iter::repeat(" ").take(max_x_chars - label_x.0.len()).collect::<String>(),
-        label_y.0);
+        label_y.0).unwrap();
 
-    println!("{}{}{}{}{}{}{}",
+    writeln!(out, "{}{}{}{}{}{}{}",
         label_x.1,
         iter::repeat(" ").take(max_x_chars - label_x.1.len()).collect::<String>(),
         label_y.1,
         iter::repeat(" ").take(max_x_chars - label_y.1.len()).collect::<String>(),
         label_x.1,
         iter::repeat(" ").take(max_x_chars - label_x.1.len()).collect::<String>(),
-        label_y.1);
+        label_y.1).unwrap();
 }
 
-pub fn print_tower(tower: &BrickTower) {
-    print_vert_labels(tower);
+// Four side views of the tower, one per direction, with a row per z level
+pub fn render_tower(tower: &BrickTower) -> String {
+    let mut out = String::new();
+    render_vert_labels(tower, &mut out);
 
     let max_dim = max(tower.bounds.1.x, tower.bounds.1.y);
     let max_z_str = tower.bounds.1.z.to_string();
@@ -161,15 +163,17 @@ pub fn print_tower(tower: &BrickTower) {
             z_str, s, 
             &z_as_str, &iter::repeat(" ").take(max_z_str.len() - z_as_str.len()).collect::<String>());
 
-        println!("{z_str} {}", if z == mid_z { "z" } else { "" });
+        writeln!(out, "{z_str} {}", if z == mid_z { "z" } else { "" }).unwrap();
     }
 
-    println!("{} 0{} {} 0{} {} 0{} {} 0", 
+    writeln!(out, "{} 0{} {} 0{} {} 0{} {} 0", 
         iter::repeat("-").take(max_dim + 1).collect::<String>(),
         iter::repeat(" ").take(max_z_str.len() - 1).collect::<String>(),
         iter::repeat("-").take(max_dim + 1).collect::<String>(),
         iter::repeat(" ").take(max_z_str.len() - 1).collect::<String>(),
         iter::repeat("-").take(max_dim + 1).collect::<String>(),
         iter::repeat(" ").take(max_z_str.len() - 1).collect::<String>(),
-        iter::repeat("-").take(max_dim + 1).collect::<String>());
-}
\ No newline at end of file
+        iter::repeat("-").take(max_dim + 1).collect::<String>()).unwrap();
+
+    out
+}
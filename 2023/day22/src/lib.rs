@@ -1,21 +1,39 @@
 mod types;
 mod debug;
 
+use aoc_runner::Answer;
 use types::BrickTower;
-use debug::print_tower;
+use debug::render_tower;
 
-pub fn part_1(input: &str, debug: bool) -> usize {
-    let mut tower = BrickTower::from_input(input);
+// size bricks of up to 4 cubes over a 10x10 area, each starting above the previous one
+pub fn generate(size: usize) -> String {
+    let mut rng = aoc_runner::Rng::new(size as u64);
+    let mut snapshot = String::new();
+    for i in 0..size {
+        let (x, y, z) = (rng.below(10), rng.below(10), 1 + 4 * i);
+        let length = rng.below(4);
+        let end = match rng.below(3) {
+            0 => ((x + length).min(9), y, z),
+            1 => (x, (y + length).min(9), z),
+            _ => (x, y, z + length),
+        };
+        snapshot += &format!("{x},{y},{z}~{},{},{}\n", end.0, end.1, end.2);
+    }
+    snapshot
+}
+
+pub fn part_1(input: &str, debug: bool) -> Answer {
+    let mut tower = aoc_runner::timed("parse", || BrickTower::from_input(input));
     if debug {
         println!("Starting Layout:\n");
-        print_tower(&tower);
+        print!("{}", render_tower(&tower));
     }
 
     tower.drop_bricks();
 
     if debug {
         println!("After Dropping:\n");
-        print_tower(&tower);
+        print!("{}", render_tower(&tower));
     
         for (idx, brick) in tower.bricks.iter().enumerate() {
             println!("Brick {},{},{}~{},{},{} supported by {}, supports {}",
@@ -41,21 +59,21 @@ pub fn part_1(input: &str, debug: bool) -> usize {
         }
     }
 
-    tower.num_removable_bricks()
+    tower.num_removable_bricks().into()
 }
 
-pub fn part_2(input: &str, debug: bool) -> usize {
-    let mut tower = BrickTower::from_input(input);
+pub fn part_2(input: &str, debug: bool) -> Answer {
+    let mut tower = aoc_runner::timed("parse", || BrickTower::from_input(input));
     if debug {
         println!("Starting Layout:\n");
-        print_tower(&tower);
+        print!("{}", render_tower(&tower));
     }
 
     tower.drop_bricks();
 
     if debug {
         println!("After Dropping:\n");
-        print_tower(&tower);
+        print!("{}", render_tower(&tower));
     
         for (idx, brick) in tower.bricks.iter().enumerate() {
             println!("Brick {},{},{}~{},{},{} supported by {}, supports {}",
@@ -83,7 +101,8 @@ pub fn part_2(input: &str, debug: bool) -> usize {
 
     (0..tower.bricks.len())
         .map(|i| tower.num_falling_if_disintegrated(i))
-        .sum()
+        .sum::<usize>()
+        .into()
 }
 
 #[cfg(test)]
@@ -124,4 +143,12 @@ mod tests {
         println!("Part 2 (Real): {result}");
         assert_eq!(result, 70702);
     }
+
+    #[test]
+    fn render_snapshot() {
+        let mut tower = BrickTower::from_input("0,0,1~2,0,1\n1,0,3~1,2,3\n0,2,5~0,2,7\n2,1,6~2,2,6\n");
+        aoc_runner::assert_snapshot!("tower_before_drop", render_tower(&tower));
+        tower.drop_bricks();
+        aoc_runner::assert_snapshot!("tower_after_drop", render_tower(&tower));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,12 @@
+aoc_runner::install_counting_allocator!();
+
+fn main() {
+    if let Some(size) = aoc_runner::generate_size() {
+        print!("{}", aoc2023_day22::generate(size));
+        return;
+    }
+
+    let input = aoc_runner::read_input(include_str!("input.txt"));
+    aoc_runner::answer!(1, aoc2023_day22::part_1(&input, false));
+    aoc_runner::answer!(2, aoc2023_day22::part_2(&input, false));
+}
@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+use aoc_graph::DotStyle;
+
 use crate::types::*;
 
 impl Maze {
@@ -11,17 +13,17 @@ impl Maze {
             let curr_point = &points[i];
             let next_point = &points[i+1];
             
-            let connection = self.direct_connections.get(curr_point).unwrap()
+            let connection = self.junctions.edges(*curr_point)
                 .iter()
                 .filter(|&c| {
                     &c.0 == next_point
                     //&& c.1 == next_point.1
                 })
-                .max_by(|a, b| a.1.cmp(&b.1))
+                .max_by(|a, b| a.1.length.cmp(&b.1.length))
                 .expect("bad path");
 
             result.pop(); // Segments have start & end
-            result.extend(&connection.2);
+            result.extend(&connection.1.cells);
         }
 
         if result.len() != path.2 + 1 { println!("Missing some points"); }
@@ -34,14 +36,16 @@ impl Maze {
         }
     }
 
-    pub fn print_path(&self, path: &Path) {
+    // The map with each cell of the path showing how many extra times it was stepped on
+    pub fn render_path(&self, path: &Path) -> String {
         let real_path = self.hydrate_path(path);
+        let mut out = String::new();
 
         for (j, row) in self.chars.iter().enumerate() {
             let mut s = String::new();
             for (i, char) in row.iter().enumerate() {
                 let point = Point::from_xy(i as i32, j as i32);
-                if &point == &self.entry {
+                if point == self.entry {
                     s += "S";
                 } else {
                     let path_steps = real_path.iter().filter(|&p| p == &point).count();
@@ -53,7 +57,18 @@ impl Maze {
                 }
             }
 
-            println!("{}", s);
+            out += &s;
+            out += "\n";
         }
+        out
+    }
+
+    // Junction graph from collapse_connections, weighted by segment length
+    pub fn to_dot(&self) -> String {
+        self.junctions.to_dot("day23", |point| {
+            if point == &self.entry { DotStyle::new("doublecircle", "palegreen") }
+            else if point == &self.exit { DotStyle::new("doublecircle", "salmon") }
+            else { DotStyle::new("circle", "lightblue") }
+        }, |segment| Some(segment.length.to_string()))
     }
-}
\ No newline at end of file
+}
@@ -1,8 +1,10 @@
 mod types;
 mod debug;
 
+use aoc_runner::Answer;
+
 fn get_longest_path_len(input: &str, ignore_slopes: bool, debug: bool) -> usize {
-    let maze = types::Maze::from_input(&input, ignore_slopes);
+    let maze = aoc_runner::timed("parse", || types::Maze::from_input(input, ignore_slopes));
     
     //if debug {
         //maze.print();
@@ -11,18 +13,29 @@ fn get_longest_path_len(input: &str, ignore_slopes: bool, debug: bool) -> usize
     let longest_path = maze.get_longest_path(debug);
 
     if debug {
-        maze.print_path(&longest_path);
+        print!("{}", maze.render_path(&longest_path));
     }
 
     longest_path.2
 }
 
-pub fn part_1(input: &str, debug: bool) -> usize {
-    get_longest_path_len(input, false, debug)
+// The longest hike drawn over the map, slopes respected
+pub fn render_longest_path(input: &str) -> String {
+    let maze = types::Maze::from_input(input, false);
+    let longest_path = maze.get_longest_path(false);
+    maze.render_path(&longest_path)
+}
+
+pub fn get_graph_dot(input: &str, ignore_slopes: bool) -> String {
+    types::Maze::from_input(input, ignore_slopes).to_dot()
+}
+
+pub fn part_1(input: &str, debug: bool) -> Answer {
+    get_longest_path_len(input, false, debug).into()
 }
 
-pub fn part_2(input: &str, debug: bool) -> usize {
-    get_longest_path_len(input, true, debug)
+pub fn part_2(input: &str, debug: bool) -> Answer {
+    get_longest_path_len(input, true, debug).into()
 }
 
 #[cfg(test)]
@@ -70,4 +83,10 @@ mod tests {
         let result = part_2(include_str!("input.txt"), false);
         assert_eq!(result, 6470);
     }
+
+    #[test]
+    fn render_snapshot() {
+        let maze = "#.#####\n#.....#\n#.#.#.#\n#...>.#\n###.#.#\n#.....#\n#####.#\n";
+        aoc_runner::assert_snapshot!("longest_path", render_longest_path(maze));
+    }
 }
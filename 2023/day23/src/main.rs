@@ -0,0 +1,20 @@
+aoc_runner::install_counting_allocator!();
+
+fn main() {
+    let input = include_str!("input.txt");
+    let args : Vec<String> = std::env::args().collect();
+    if args.iter().any(|a| a == "--graph") {
+        let ignore_slopes = args.iter().any(|a| a == "--ignore-slopes");
+        print!("{}", aoc2023_day23::get_graph_dot(input, ignore_slopes));
+        return;
+    }
+
+    if args.iter().any(|a| a == "--render") {
+        print!("{}", aoc2023_day23::render_longest_path(input));
+        return;
+    }
+
+    aoc_runner::answer!(1, aoc2023_day23::part_1(input, false));
+
+    aoc_runner::answer!(2, aoc2023_day23::part_2(input, false));
+}
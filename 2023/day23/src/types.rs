@@ -1,4 +1,6 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::{collections::{HashMap, HashSet, VecDeque}, fmt::Display};
+
+use aoc_graph::{Graph, NodeId};
 
 pub type Direction = (i32, i32);
 const DIR_UP: Direction = (0, -1);
@@ -6,10 +8,18 @@ const DIR_DOWN: Direction = (0, 1);
 const DIR_LEFT: Direction = (-1, 0);
 const DIR_RIGHT: Direction = (1, 0);
 
-// Note: would be faster as a HashSet<Point>, but need the order for debug draw
-pub type Path = (Vec<Point>, Point, usize);
+// Junctions visited in order, the last one and the length so far.
+// Note: would be faster as a HashSet<NodeId>, but need the order for debug draw
+pub type Path = (Vec<NodeId>, NodeId, usize);
+
+// The cells from one junction to the next, both ends included, and how many steps that takes
+#[derive(Debug, Clone)]
+pub struct Segment {
+    pub length: usize,
+    pub cells: Vec<Point>,
+}
 
-#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, PartialOrd, Ord)]
 pub struct Point {
     x: i32,
     y: i32
@@ -26,6 +36,12 @@ impl Point {
     }
 }
 
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
 #[derive(Debug)]
 pub struct Maze
 {
@@ -33,9 +49,10 @@ pub struct Maze
     dim: (usize, usize),
     ignore_slopes: bool,
     pub entry: Point,
-    exit: Point,
+    pub exit: Point,
     pub connections: HashMap<Point, HashSet<Point>>,
-    pub direct_connections: HashMap<Point, Vec<(Point, usize, Vec<Point>)>>,
+    // Junctions (and the entry and exit) joined by the segments between them
+    pub junctions: Graph<Point, Segment>,
 }
 impl Maze {
     pub fn from_input(input: &str, ignore_slopes: bool) -> Self {
@@ -51,7 +68,7 @@ impl Maze {
             entry: Point { x: 0, y: 0 },
             exit: Point { x: 0, y: 0 },
             connections: HashMap::new(),
-            direct_connections: HashMap::new(),
+            junctions: Graph::new_directed(),
         };
 
         {
@@ -121,6 +138,7 @@ impl Maze {
         let mut visited_points = HashSet::new();
         let mut point_queue = VecDeque::new();
 
+        self.junctions.add_node(start_point);
         point_queue.push_back(start_point);
 
         while let Some(p) = point_queue.pop_front() {
@@ -139,9 +157,9 @@ impl Maze {
     
                         let segment_end = *segment.last().unwrap();
                         
-                        self.direct_connections
-                            .entry(p).or_default()
-                            .push((segment_end, segment.len() - 1, segment));
+                        let from = self.junctions.add_node(p);
+                        let to = self.junctions.add_node(segment_end);
+                        self.junctions.add_edge(from, to, Segment { length: segment.len() - 1, cells: segment });
     
                         point_queue.push_back(segment_end);
                     }
@@ -200,7 +218,9 @@ impl Maze {
     }
 
     pub fn get_longest_path(&self, debug: bool) -> Path {
-        let mut longest_path : Path = (vec![ self.entry ], self.entry, 0);
+        let entry = self.junctions.id(&self.entry).unwrap();
+        let exit = self.junctions.id(&self.exit);
+        let mut longest_path : Path = (vec![ entry ], entry, 0);
         let mut considered_paths = 0;
 
         let mut path_queue : Vec<Path> = Vec::new();
@@ -208,8 +228,8 @@ impl Maze {
 
         while let Some(path) = path_queue.pop() {
             if debug { println!("Testing path: {path:?}"); }
-            let last_point = &path.1;
-            if last_point == &self.exit {
+            let last_point = path.1;
+            if Some(last_point) == exit {
                 // Reached the end!
                 if path.2 > longest_path.2 {
                     longest_path = path;
@@ -218,35 +238,33 @@ impl Maze {
                 continue;
             }
 
-            if let Some(connections) = self.direct_connections.get(&last_point) {
-                let connections = connections.iter()
-                    .filter(|&c| !path.0.contains(&c.0));
-
-                if self.ignore_slopes {
-                    if let Some(exit_path) = connections.clone()
-                        .find(|&c| &c.0 == &self.exit)
-                    {
-                        let mut path = path.clone();
-                        path.0.push(exit_path.0);
-                        path.1 = exit_path.0;
-                        path.2 += exit_path.1;
-
-                        path_queue.push(path);
-                        continue;
-                    }
-                }
-                
-                for connection in connections {
+            let connections = self.junctions.edges(last_point).iter()
+                .filter(|&c| !path.0.contains(&c.0));
+
+            if self.ignore_slopes {
+                if let Some(exit_path) = connections.clone()
+                    .find(|&c| Some(c.0) == exit)
+                {
                     let mut path = path.clone();
-                    path.0.push(connection.0);
-                    path.1 = connection.0;
-                    path.2 += connection.1;
+                    path.0.push(exit_path.0);
+                    path.1 = exit_path.0;
+                    path.2 += exit_path.1.length;
+
                     path_queue.push(path);
+                    continue;
                 }
             }
+            
+            for connection in connections {
+                let mut path = path.clone();
+                path.0.push(connection.0);
+                path.1 = connection.0;
+                path.2 += connection.1.length;
+                path_queue.push(path);
+            }
         }
 
-        println!("Found {considered_paths} total paths");
+        if debug { println!("Found {considered_paths} total paths"); }
         longest_path
     }
 }
\ No newline at end of file
@@ -1,15 +1,14 @@
 mod types;
 
+use aoc_runner::{Answer, Explained};
 use types::Hailstorm;
 
-#[allow(dead_code)]
-fn part_1(input: &str, bounds: (i64, i64), debug: bool) -> usize {
+pub fn part_1(input: &str, bounds: (i64, i64), debug: bool) -> Answer {
     let snowstorm = Hailstorm::from_input(input);
-    snowstorm.count_intersections((bounds.0, bounds.1, i64::MAX), debug)
+    snowstorm.count_intersections((bounds.0, bounds.1, i64::MAX), debug).into()
 }
 
-#[allow(dead_code)]
-fn part_2(input: &str, debug: bool) -> i64 {
+pub fn part_2(input: &str, debug: bool) -> Explained<Answer> {
     let hailstorm = Hailstorm::from_input(input);
     let rock = hailstorm.find_common_rock();
     if debug {
@@ -17,7 +16,9 @@ fn part_2(input: &str, debug: bool) -> i64 {
             rock.pos.x, rock.pos.y, rock.pos.z,
             rock.vel.x, rock.vel.y, rock.vel.z);
     }
-    rock.pos.x + rock.pos.y + rock.pos.z
+    Explained::new((rock.pos.x + rock.pos.y + rock.pos.z).into())
+        .with("rock_position", &[rock.pos.x, rock.pos.y, rock.pos.z])
+        .with("rock_velocity", &[rock.vel.x, rock.vel.y, rock.vel.z])
 }
 
 #[cfg(test)]
@@ -40,14 +41,14 @@ mod tests {
 
     #[test]
     fn part2_sample() {
-        let result = part_2(include_str!("sample.txt"), true);
+        let result = part_2(include_str!("sample.txt"), true).result;
         println!("Part 2 (Sample): {result}");
         assert_eq!(result, 47);
     }
 
     #[test]
     fn part2_input() {
-        let result = part_2(include_str!("input.txt"), false);
+        let result = part_2(include_str!("input.txt"), false).result;
         println!("Part 2 (Real): {result}");
         assert_eq!(result, 908621716620524);
     }
@@ -0,0 +1,9 @@
+aoc_runner::install_counting_allocator!();
+
+fn main() {
+    let input = include_str!("input.txt");
+
+    aoc_runner::answer!(1, aoc2023_day24::part_1(input, (200000000000000, 400000000000000), false));
+
+    aoc_runner::explain!(2, aoc2023_day24::part_2(input, false));
+}
@@ -2,16 +2,49 @@
 
 mod types;
 
+use aoc_runner::{Answer, Assumption, Explained};
 use types::Diagram;
 
-fn part_1(input: &str, debug: bool) -> usize {
+pub fn get_graph_dot(input: &str) -> String {
+    Diagram::from_input(input).to_dot()
+}
+
+// Part 1 cuts every wire that is not part of a 4-cycle, which only finds the three wires to cut
+// when the rest of the machine is tightly enough knit that every other wire is in one
+const PART_1_ASSUMPTIONS : &[Assumption<Diagram>] = &[
+    Assumption { name: "exactly three wires are in no 4-cycle", check: |diagram| {
+        match diagram.get_standalone_wires(false).len() {
+            3 => Ok(()),
+            count => Err(format!("{count} wires are in no 4-cycle")),
+        }
+    } },
+    Assumption { name: "cutting those wires splits the machine in two", check: |diagram| {
+        let mut cut = diagram.clone();
+        cut.remove_connections(&diagram.get_standalone_wires(false));
+        match cut.get_group_sizes(false).len() {
+            2 => Ok(()),
+            count => Err(format!("it leaves {count} groups")),
+        }
+    } },
+];
+
+// Prints each assumption part 1 relies on that this input breaks
+pub fn part_1_assumptions_hold(input: &'static str) -> bool {
+    aoc_runner::check_assumptions(1, &Diagram::from_input(input), PART_1_ASSUMPTIONS)
+}
+
+pub fn part_1(input: &str, debug: bool) -> Explained<Answer> {
     let mut diagram = Diagram::from_input(input);
     if debug { println!("{diagram:#?}"); }
     let wires_to_cut = diagram.get_standalone_wires(debug);
     diagram.remove_connections(&wires_to_cut);
     let result = diagram.get_group_sizes(debug);
     assert_eq!(result.len(), 2);
-    result[0] * result[1]
+
+    let wires_cut : Vec<String> = wires_to_cut.iter().map(|(a, b)| format!("{a}/{b}")).collect();
+    Explained::new((result[0] * result[1]).into())
+        .with("wires_cut", &wires_cut)
+        .with("group_sizes", &result)
 }
 
 fn part_1_direct(input: &str, wires_to_cut: &Vec<(&str, &str)>, debug: bool) -> usize {
@@ -31,10 +64,22 @@ mod tests {
 
     #[test]
     fn part1_sample() {
-        let result = part_1(include_str!("sample.txt"), false);
+        let result = part_1(include_str!("sample.txt"), false).result;
         assert_eq!(result, 54);
     }
 
+    #[test]
+    fn part1_assumptions() {
+        assert!(part_1_assumptions_hold(include_str!("sample.txt")));
+
+        // A chain has no 4-cycles at all, so every wire looks like one to cut
+        let failures = aoc_runner::failed_assumptions(&Diagram::from_input("a: b\nb: c"), PART_1_ASSUMPTIONS);
+        assert_eq!(failures, vec![
+            "assumption 'exactly three wires are in no 4-cycle' does not hold for this input: 2 wires are in no 4-cycle",
+            "assumption 'cutting those wires splits the machine in two' does not hold for this input: it leaves 3 groups",
+        ]);
+    }
+
     #[test]
     fn part1_input() {
         let result = part_1_direct(include_str!("input.txt"),
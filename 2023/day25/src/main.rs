@@ -0,0 +1,13 @@
+aoc_runner::install_counting_allocator!();
+
+fn main() {
+    let input = include_str!("input.txt");
+    if std::env::args().any(|a| a == "--graph") {
+        print!("{}", aoc2023_day25::get_graph_dot(input));
+        return;
+    }
+
+    if aoc2023_day25::part_1_assumptions_hold(input) {
+        aoc_runner::explain!(1, aoc2023_day25::part_1(input, false));
+    }
+}
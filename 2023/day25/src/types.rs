@@ -1,21 +1,22 @@
-use std::collections::{HashMap, HashSet};
+use aoc_graph::{DotStyle, Graph, NodeId};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Diagram<'a> {
-    connections: HashMap<&'a str, HashSet<&'a str>>,
+    connections: Graph<&'a str>,
 }
 impl<'a> Diagram<'a> {
     pub fn from_input<'b>(input: &'b str) -> Diagram where 'b: 'a {
-        let mut connections : HashMap<&str, HashSet<&str>> = HashMap::new();
+        let mut connections : Graph<&str> = Graph::new_undirected();
 
         for line in input.lines() {
             let line_parts : Vec<&str> = line.split(": ").collect();
-            let from_part = line_parts[0].trim();
-            let to_parts : HashSet<&str> = line_parts[1].split(" ").map(|x| x.trim()).collect();
+            let from_part = connections.add_node(line_parts[0].trim());
 
-            for to_part in to_parts {
-                connections.entry(from_part).or_default().insert(to_part);
-                connections.entry(to_part).or_default().insert(from_part);
+            for to_part in line_parts[1].split(" ").map(|x| x.trim()) {
+                let to_part = connections.add_node(to_part);
+                if !connections.has_edge(from_part, to_part) {
+                    connections.add_edge(from_part, to_part, ());
+                }
             }
         }
 
@@ -25,28 +26,29 @@ impl<'a> Diagram<'a> {
     }
 
     pub fn get_standalone_wires(&self, debug: bool) -> Vec<(&'a str, &'a str)> {
-        let mut result = Vec::new();
+        let mut result : Vec<(NodeId, NodeId)> = Vec::new();
+        let graph = &self.connections;
+        let name = |id: NodeId| *graph.label(id);
 
-        for (&node, connections) in &self.connections {
-            if debug { println!("Testing node {node}..."); }
+        for node in graph.nodes() {
+            if debug { println!("Testing node {}...", name(node)); }
 
-            for &connection_a in connections {
+            for connection_a in graph.neighbors(node) {
                 let mut has_transitive = false;
 
-                for &connection_b in connections {
+                for connection_b in graph.neighbors(node) {
                     if connection_a == connection_b {
                         continue;
                     }
 
-                    let b_connections = self.connections.get(connection_b).unwrap();
-                    for &other in b_connections {
+                    for other in graph.neighbors(connection_b) {
                         if other == node || other == connection_a {
                             continue;
                         }
 
-                        let other_other = self.connections.get(other).unwrap();
-                        if other_other.contains(connection_a) {
-                            if debug { println!(" + Node {node} has connection: {node} -> {connection_a} -> {connection_b} -> {other} -> {node}"); }
+                        if graph.has_edge(other, connection_a) {
+                            if debug { println!(" + Node {} has connection: {} -> {} -> {} -> {} -> {}",
+                                name(node), name(node), name(connection_a), name(connection_b), name(other), name(node)); }
                             has_transitive = true;
                             break;
                         }
@@ -58,7 +60,7 @@ impl<'a> Diagram<'a> {
                 }
 
                 if !has_transitive {
-                    if debug { println!(" + Connection {node}->{connection_a} has no transitive connection, marking as candidate!")};
+                    if debug { println!(" + Connection {}->{} has no transitive connection, marking as candidate!", name(node), name(connection_a))};
                     if !result.contains(&(connection_a, node)) {
                         if debug { println!(" + Pushing..."); }
                         result.push((node, connection_a));
@@ -67,46 +69,36 @@ impl<'a> Diagram<'a> {
             }
         }
 
-        if debug { println!("{result:#?}"); }
+        let result : Vec<(&'a str, &'a str)> = result.into_iter()
+            .map(|(a, b)| (name(a), name(b)))
+            .collect();
 
-        assert_eq!(result.len(), 3);
+        if debug { println!("{result:#?}"); }
 
         result
     }
 
     pub fn remove_connections(&mut self, connections: &Vec<(&'a str, &'a str)>) {
         for &connection in connections {
-            self.connections.get_mut(connection.0).unwrap().remove(connection.1);
-            self.connections.get_mut(connection.1).unwrap().remove(connection.0);
+            let from = self.connections.id(&connection.0).unwrap();
+            let to = self.connections.id(&connection.1).unwrap();
+            self.connections.remove_edge(from, to);
         }
     }
 
     pub fn get_group_sizes(&self, debug: bool) -> Vec<usize> {
-        let mut groups : Vec<HashSet<&str>> = Vec::new();
-
-        for &node in self.connections.keys() {
-            if groups.iter().find(|s| s.contains(node)).is_some() {
-                continue;
-            }
-
-            let mut group = HashSet::new();
+        let groups = self.connections.connected_components();
 
-            let mut group_stack = Vec::new();
-            group_stack.push(node);
-
-            while let Some(n) = group_stack.pop() {
-                if group.contains(n) {
-                    continue;
-                }
-
-                group.insert(n);
-                group_stack.extend(self.connections.get(n).unwrap());
-            }
-
-            groups.push(group);
+        if debug {
+            let named_groups : Vec<Vec<&str>> = groups.iter()
+                .map(|g| g.iter().map(|id| *self.connections.label(*id)).collect())
+                .collect();
+            println!("Groups: {named_groups:#?}");
         }
-
-        if debug { println!("Groups: {groups:#?}"); }
         groups.iter().map(|g| g.len()).collect()
     }
-}
\ No newline at end of file
+
+    pub fn to_dot(&self) -> String {
+        self.connections.to_dot("day25", |_| DotStyle::default(), |_| None)
+    }
+}
@@ -0,0 +1,209 @@
+use std::{collections::HashMap, fmt::Write};
+
+use aoc_graph::{DotStyle, Graph};
+use aoc_runner::Grid;
+
+// A run of digits on one row, `len` cells wide starting at `col`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberSpan {
+    pub value: u64,
+    pub row: usize,
+    pub col: usize,
+    pub len: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub symbol: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+// Every number and symbol in the engine schematic, and which of them touch. The adjacency is
+// bipartite: numbers only ever touch symbols and symbols only ever touch numbers.
+#[derive(Debug)]
+pub struct Schematic {
+    pub grid: Grid<char>,
+    pub numbers: Vec<NumberSpan>,
+    pub symbols: Vec<Symbol>,
+    // By index into numbers and symbols
+    number_symbols: Vec<Vec<usize>>,
+    symbol_numbers: Vec<Vec<usize>>,
+}
+impl Schematic {
+    // Anything that isn't a digit, a letter or '.' is a symbol
+    pub fn from_input(input: &str) -> Schematic {
+        let grid = Grid::parse(input, |c| c);
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+
+        for (row, cells) in grid.rows().enumerate() {
+            let mut col = 0;
+            while col < cells.len() {
+                let c = cells[col];
+                if let Some(digit) = c.to_digit(10) {
+                    let mut span = NumberSpan { value: digit as u64, row, col, len: 1 };
+                    while let Some(digit) = cells.get(col + span.len).and_then(|c| c.to_digit(10)) {
+                        span.value = span.value * 10 + digit as u64;
+                        span.len += 1;
+                    }
+                    col += span.len;
+                    numbers.push(span);
+                    continue;
+                }
+
+                if c != '.' && !c.is_alphabetic() {
+                    symbols.push(Symbol { symbol: c, row, col });
+                }
+                col += 1;
+            }
+        }
+
+        let symbol_at : HashMap<(usize, usize), usize> = symbols.iter().enumerate()
+            .map(|(i, s)| ((s.row, s.col), i))
+            .collect();
+        let mut number_symbols = vec![Vec::new(); numbers.len()];
+        let mut symbol_numbers = vec![Vec::new(); symbols.len()];
+        for (n, number) in numbers.iter().enumerate() {
+            for row in number.row.saturating_sub(1)..=number.row + 1 {
+                for col in number.col.saturating_sub(1)..=number.col + number.len {
+                    if let Some(&s) = symbol_at.get(&(row, col)) {
+                        number_symbols[n].push(s);
+                        symbol_numbers[s].push(n);
+                    }
+                }
+            }
+        }
+
+        Schematic { grid, numbers, symbols, number_symbols, symbol_numbers }
+    }
+
+    pub fn numbers_touching(&self, symbol: usize) -> impl Iterator<Item = &NumberSpan> {
+        self.symbol_numbers[symbol].iter().map(|&n| &self.numbers[n])
+    }
+
+    // Numbers next to at least one symbol
+    pub fn part_numbers(&self) -> impl Iterator<Item = &NumberSpan> {
+        self.numbers.iter().enumerate()
+            .filter(|(n, _)| !self.number_symbols[*n].is_empty())
+            .map(|(_, number)| number)
+    }
+
+    // Numbers no symbol touches
+    pub fn orphaned_numbers(&self) -> impl Iterator<Item = &NumberSpan> {
+        self.numbers.iter().enumerate()
+            .filter(|(n, _)| self.number_symbols[*n].is_empty())
+            .map(|(_, number)| number)
+    }
+
+    // Each `symbol` touching exactly `parts` numbers, with those numbers. The puzzle's gears are
+    // gears('*', 2).
+    pub fn gears(&self, symbol: char, parts: usize) -> impl Iterator<Item = (&Symbol, Vec<&NumberSpan>)> {
+        self.symbols.iter().enumerate()
+            .filter(move |(s, gear)| gear.symbol == symbol && self.symbol_numbers[*s].len() == parts)
+            .map(|(s, gear)| (gear, self.numbers_touching(s).collect()))
+    }
+
+    // Symbols of any kind touching at least `parts` numbers
+    pub fn crowded_symbols(&self, parts: usize) -> impl Iterator<Item = (&Symbol, Vec<&NumberSpan>)> {
+        self.symbols.iter().enumerate()
+            .filter(move |(s, _)| self.symbol_numbers[*s].len() >= parts)
+            .map(|(s, symbol)| (symbol, self.numbers_touching(s).collect()))
+    }
+
+    // The grid followed by every number and symbol, labelled N<i> and S<i>, and what each touches
+    pub fn annotate(&self) -> String {
+        let mut out = String::new();
+        for row in self.grid.rows() {
+            writeln!(out, "{}", row.iter().collect::<String>()).unwrap();
+        }
+
+        writeln!(out, "\nNumbers:").unwrap();
+        for (n, number) in self.numbers.iter().enumerate() {
+            let touching : Vec<String> = self.number_symbols[n].iter()
+                .map(|&s| format!("S{s} {}", self.symbols[s].symbol))
+                .collect();
+            let touching = if touching.is_empty() { String::from("orphaned") } else { touching.join(", ") };
+            writeln!(out, "  N{n} {} at row {} cols {}-{} -> {touching}",
+                number.value, number.row, number.col, number.col + number.len - 1).unwrap();
+        }
+
+        writeln!(out, "Symbols:").unwrap();
+        for (s, symbol) in self.symbols.iter().enumerate() {
+            let touching : Vec<String> = self.symbol_numbers[s].iter()
+                .map(|&n| format!("N{n} {}", self.numbers[n].value))
+                .collect();
+            writeln!(out, "  S{s} {} at row {} col {} -> {}",
+                symbol.symbol, symbol.row, symbol.col, touching.join(", ")).unwrap();
+        }
+        out
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut graph : Graph<String> = Graph::new_undirected();
+        let number_ids : Vec<_> = self.numbers.iter().enumerate()
+            .map(|(n, number)| graph.add_node(format!("N{n} {}", number.value)))
+            .collect();
+        for (s, symbol) in self.symbols.iter().enumerate() {
+            let id = graph.add_node(format!("S{s} {}", symbol.symbol));
+            for &n in &self.symbol_numbers[s] {
+                graph.add_edge(id, number_ids[n], ());
+            }
+        }
+
+        graph.to_dot("day3", |label| {
+            if label.starts_with('S') { DotStyle::new("diamond", "orange") }
+            else { DotStyle::new("box", "lightblue") }
+        }, |_| None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE : &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn sample_queries() {
+        let schematic = Schematic::from_input(SAMPLE);
+
+        assert_eq!(schematic.part_numbers().map(|n| n.value).sum::<u64>(), 4361);
+        let orphans : Vec<u64> = schematic.orphaned_numbers().map(|n| n.value).collect();
+        assert_eq!(orphans, vec![114, 58]);
+
+        let ratios : Vec<u64> = schematic.gears('*', 2)
+            .map(|(_, parts)| parts.iter().map(|p| p.value).product())
+            .collect();
+        assert_eq!(ratios, vec![16345, 451490]);
+        assert_eq!(schematic.gears('*', 1).count(), 1);
+        assert_eq!(schematic.gears('#', 1).count(), 1);
+        assert_eq!(schematic.crowded_symbols(3).count(), 0);
+    }
+
+    #[test]
+    fn edges_and_crowding() {
+        // Numbers on the grid's edges, and one symbol touching four of them
+        let schematic = Schematic::from_input("12.34\n..*..\n.5.67");
+
+        let crowded : Vec<(char, Vec<u64>)> = schematic.crowded_symbols(3)
+            .map(|(s, parts)| (s.symbol, parts.iter().map(|p| p.value).collect()))
+            .collect();
+        assert_eq!(crowded, vec![('*', vec![12, 34, 5, 67])]);
+        assert_eq!(schematic.gears('*', 4).count(), 1);
+
+        let annotated = schematic.annotate();
+        assert!(annotated.starts_with("12.34\n..*..\n.5.67\n\nNumbers:\n  N0 12 at row 0 cols 0-1 -> S0 *\n"));
+        assert!(annotated.ends_with("Symbols:\n  S0 * at row 1 col 2 -> N0 12, N1 34, N2 5, N3 67\n"));
+    }
+}
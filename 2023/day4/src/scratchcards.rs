@@ -0,0 +1,272 @@
+use std::{collections::{HashSet, VecDeque}, fmt};
+
+pub struct LotteryTicket
+{
+    pub card_num: u32,
+    winning_num: HashSet<i32>,
+    my_num: HashSet<i32>
+}
+impl LotteryTicket {
+    pub fn parse(line: &str) -> LotteryTicket {
+        let (card_id, card_numbers) = line.split_once(':')
+            .unwrap_or_else(|| panic!("Line was not in proper format: {line}"));
+        let (winning_nums, my_nums) = card_numbers.split_once('|')
+            .unwrap_or_else(|| panic!("Line was not in proper format: {line}"));
+
+        LotteryTicket {
+            card_num: parse_card_id(card_id),
+            winning_num: parse_numbers(winning_nums),
+            my_num: parse_numbers(my_nums),
+        }
+    }
+
+    pub fn get_score(&self, scoring: Scoring) -> Option<u64> {
+        scoring.score(self.num_matches())
+    }
+
+    pub fn num_matches(&self) -> usize {
+        self.my_num.iter()
+            .filter(|x| self.winning_num.contains(x))
+            .count()
+    }
+}
+
+fn parse_numbers(line: &str) -> HashSet<i32> {
+    line.trim().split(' ')
+        .filter(|x| !x.is_empty())
+        .map(|x| x.parse::<i32>().expect("Line is not in proper format"))
+        .collect()
+}
+
+fn parse_card_id(line: &str) -> u32 {
+    line.split_whitespace()
+        .find_map(|x| x.parse::<u32>().ok())
+        .expect("Did not find a valid card ID")
+}
+
+// Points a card is worth for its number of matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scoring {
+    // 1 point for the first match, doubled for each one after it
+    Doubling,
+    // The same points for every match
+    Linear(u64),
+}
+impl Scoring {
+    // None if the points don't fit in 64 bits, which doubling reaches at 65 matches
+    pub fn score(&self, matches: usize) -> Option<u64> {
+        match self {
+            Scoring::Doubling if matches == 0 => Some(0),
+            Scoring::Doubling => u32::try_from(matches - 1).ok()
+                .and_then(|shift| 1u64.checked_shl(shift)),
+            Scoring::Linear(points) => points.checked_mul(matches as u64),
+        }
+    }
+
+    // `doubling`, `linear` or `linear:<points>`
+    pub fn parse(text: &str) -> Result<Scoring, String> {
+        match text.split_once(':') {
+            None if text == "doubling" => Ok(Scoring::Doubling),
+            None if text == "linear" => Ok(Scoring::Linear(1)),
+            Some(("linear", points)) => points.parse().map(Scoring::Linear)
+                .map_err(|_| format!("'{points}' is not a number of points")),
+            _ => Err(format!("unknown scoring '{text}', expected doubling, linear or linear:<points>")),
+        }
+    }
+}
+
+// How many of the following cards a winning card copies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyWindow {
+    // One card per match
+    Matches,
+    // `n` cards per match
+    Scaled(usize),
+    // Always `n` cards, once there is any match at all
+    Fixed(usize),
+}
+impl CopyWindow {
+    pub fn size(&self, matches: usize) -> usize {
+        match self {
+            CopyWindow::Matches => matches,
+            CopyWindow::Scaled(n) => n * matches,
+            CopyWindow::Fixed(_) if matches == 0 => 0,
+            CopyWindow::Fixed(n) => *n,
+        }
+    }
+
+    // `matches`, `<n>x` or `<n>`
+    pub fn parse(text: &str) -> Result<CopyWindow, String> {
+        let number = |n: &str| n.parse::<usize>().map_err(|_| format!("invalid copy window '{text}'"));
+        match text.strip_suffix('x') {
+            _ if text == "matches" => Ok(CopyWindow::Matches),
+            Some(n) => number(n).map(CopyWindow::Scaled),
+            None => number(text).map(CopyWindow::Fixed),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub scoring: Scoring,
+    pub copy_window: CopyWindow,
+}
+impl Default for Rules {
+    fn default() -> Self {
+        Rules { scoring: Scoring::Doubling, copy_window: CopyWindow::Matches }
+    }
+}
+
+// What one card did: its matches and points, and how many copies of it were held
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardResult {
+    pub card_num: u32,
+    pub matches: usize,
+    pub score: Option<u64>,
+    pub copies: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NumberingIssue {
+    // Card numbers skipped between two cards
+    Gap { from: u32, to: u32 },
+    Duplicate(u32),
+    OutOfOrder { card_num: u32, after: u32 },
+}
+impl fmt::Display for NumberingIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NumberingIssue::Gap { from, to } if from == to => write!(f, "card {from} is missing"),
+            NumberingIssue::Gap { from, to } => write!(f, "cards {from} to {to} are missing"),
+            NumberingIssue::Duplicate(card_num) => write!(f, "card {card_num} appears more than once"),
+            NumberingIssue::OutOfOrder { card_num, after } => write!(f, "card {card_num} comes after card {after}"),
+        }
+    }
+}
+
+// Plays cards one at a time under a set of rules. Cards are expected to be numbered 1, 2, 3...
+// and anything else is recorded as an issue. Copies won for missing cards are lost, and
+// duplicate or out of order cards take the next position as they come.
+pub struct Scratchcards {
+    rules: Rules,
+    // Extra copies won by earlier cards, for the cards coming up next. Only ever as long as the
+    // largest copy window, so memory stays bounded however many cards there are
+    pending_copies: VecDeque<u64>,
+    last_card: u32,
+    pub issues: Vec<NumberingIssue>,
+    // None once the points no longer fit in 64 bits
+    pub total_score: Option<u64>,
+    pub total_cards: u64,
+}
+impl Scratchcards {
+    pub fn new(rules: Rules) -> Self {
+        Scratchcards { rules, pending_copies: VecDeque::new(), last_card: 0, issues: Vec::new(), total_score: Some(0), total_cards: 0 }
+    }
+
+    fn check_numbering(&mut self, card_num: u32) {
+        if card_num == self.last_card {
+            self.issues.push(NumberingIssue::Duplicate(card_num));
+        } else if card_num < self.last_card {
+            self.issues.push(NumberingIssue::OutOfOrder { card_num, after: self.last_card });
+        } else {
+            if card_num > self.last_card + 1 {
+                self.issues.push(NumberingIssue::Gap { from: self.last_card + 1, to: card_num - 1 });
+                let missing = (card_num - self.last_card - 1) as usize;
+                self.pending_copies.drain(..missing.min(self.pending_copies.len()));
+            }
+            self.last_card = card_num;
+        }
+    }
+
+    pub fn play(&mut self, ticket: &LotteryTicket) -> CardResult {
+        self.check_numbering(ticket.card_num);
+
+        let copies = 1 + self.pending_copies.pop_front().unwrap_or(0);
+        let matches = ticket.num_matches();
+        let score = ticket.get_score(self.rules.scoring);
+        self.total_cards += copies;
+        self.total_score = self.total_score.zip(score).and_then(|(total, score)| total.checked_add(score));
+
+        let window = self.rules.copy_window.size(matches);
+        if self.pending_copies.len() < window {
+            self.pending_copies.resize(window, 0);
+        }
+        for next_count in self.pending_copies.iter_mut().take(window) {
+            *next_count += copies;
+        }
+
+        CardResult { card_num: ticket.card_num, matches, score, copies }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE : &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    fn play(input: &str, rules: Rules) -> (Scratchcards, Vec<CardResult>) {
+        let mut cards = Scratchcards::new(rules);
+        let results = input.lines().map(|l| cards.play(&LotteryTicket::parse(l))).collect();
+        (cards, results)
+    }
+
+    #[test]
+    fn sample_trace() {
+        let (cards, results) = play(SAMPLE, Rules::default());
+        assert_eq!(cards.total_score, Some(13));
+        assert_eq!(cards.total_cards, 30);
+        assert!(cards.issues.is_empty());
+
+        let trace : Vec<(usize, u64, u64)> = results.iter().map(|r| (r.matches, r.score.unwrap(), r.copies)).collect();
+        assert_eq!(trace, vec![(4, 8, 1), (2, 2, 2), (2, 2, 4), (1, 1, 8), (0, 0, 14), (0, 0, 1)]);
+    }
+
+    #[test]
+    fn other_rules() {
+        let rules = Rules { scoring: Scoring::parse("linear:2").unwrap(), copy_window: CopyWindow::parse("1").unwrap() };
+        let (cards, results) = play(SAMPLE, rules);
+        assert_eq!(cards.total_score, Some(18));
+        assert_eq!(results.iter().map(|r| r.copies).collect::<Vec<u64>>(), vec![1, 2, 3, 4, 5, 1]);
+
+        assert_eq!(CopyWindow::parse("2x"), Ok(CopyWindow::Scaled(2)));
+        assert_eq!(CopyWindow::Scaled(2).size(3), 6);
+        assert!(Scoring::parse("squared").is_err());
+    }
+
+    #[test]
+    fn numbering_issues() {
+        let lines : Vec<&str> = SAMPLE.lines().collect();
+        let shuffled = [lines[0], lines[3], lines[3], lines[2], lines[5]].join("\n");
+        let (cards, results) = play(&shuffled, Rules::default());
+
+        let issues : Vec<String> = cards.issues.iter().map(|i| i.to_string()).collect();
+        assert_eq!(issues, vec!["cards 2 to 3 are missing", "card 4 appears more than once", "card 3 comes after card 4", "card 5 is missing"]);
+        // Card 1 won copies of 2 to 5, so with 2 and 3 missing card 4 has one extra
+        assert_eq!(results[1].copies, 2);
+    }
+
+    #[test]
+    fn score_overflow() {
+        assert_eq!(Scoring::Doubling.score(64), Some(1 << 63));
+        assert_eq!(Scoring::Doubling.score(65), None);
+        assert_eq!(Scoring::Doubling.score(usize::MAX), None);
+        assert_eq!(Scoring::Linear(u64::MAX).score(2), None);
+
+        let winning : Vec<String> = (1..=65).map(|n| n.to_string()).collect();
+        let line = format!("Card 1: {} | {}", winning.join(" "), winning.join(" "));
+        let (cards, results) = play(&format!("{line}\nCard 2: 1 | 1"), Rules::default());
+        assert_eq!(results[0].score, None);
+        assert_eq!(results[1].score, Some(1));
+        assert_eq!(cards.total_score, None);
+
+        let (cards, _) = play(&format!("{line}\nCard 2: 1 | 1"), Rules { scoring: Scoring::Linear(1), ..Rules::default() });
+        assert_eq!(cards.total_score, Some(66));
+    }
+}
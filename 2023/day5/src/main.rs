@@ -1,106 +1,63 @@
 use std::{str::Lines, time::SystemTime};
 
-#[derive(Debug, Copy, Clone)]
+use aoc_runner::Answer;
+
+mod piecewise;
+
+use piecewise::PiecewiseMap;
+
+// Inclusive at both ends
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 struct Range {
     start: i64,
     end: i64,
 }
-impl Range {
-    fn contains_range(&self, range: &Range) -> bool {
-        self.start <= range.start
-            && self.end >= range.end
-    }
-}
 
 #[derive(Debug)]
 struct MapConversion {
     source: Range,
     dest: Range,
 }
-impl MapConversion {
-    fn convert_range(&self, range: &Range) -> Range {
-        if self.source.contains_range(range) {
-            let start = (range.start - self.source.start) + self.dest.start;
-            let end = (range.end - range.start) + start;
-            return Range { start, end };
-        }
-        
-        return *range;
-    }
+
+// What is wrong with an almanac's maps. Errors leave a value with more than one conversion, so
+// there is no single answer. Warnings are odd but still well defined.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Validation {
+    errors: Vec<String>,
+    warnings: Vec<String>,
 }
 
 #[derive(Debug)]
 struct AlmanacMap {
+    name: String,
     conversions: Vec<MapConversion>,
 }
 impl AlmanacMap {
-    fn split_ranges(&self, range: &Range) -> Vec<Range> {
-        let mut queue : Vec<Range> = Vec::new();
-        queue.push(*range);
-
-        let mut result : Vec<Range> = Vec::new();
-
-        loop {
-            match queue.pop() {
-                Some(r) => {
-                    let mut found_match = false;
-                    for conversion in &self.conversions {
-                        let start = r.start;
-                        let end = r.end;
-
-                        if start < conversion.source.start {
-                            if end < conversion.source.start {
-                                // fully before this entry
-                                result.push(Range { start, end });
-                            } else {
-                                result.push(Range { start, end: conversion.source.start - 1 });
-                                
-                                // process next time
-                                queue.push(Range { start: conversion.source.start, end });
-                            }                            
-
-                            found_match = true;
-                            break;
-                        } else if start <= conversion.source.end {
-                            if end <= conversion.source.end {
-                                // fully inside this entry
-                                result.push(Range { start, end });
-                            } else {
-                                result.push(Range { start, end: conversion.source.end });
-
-                                // process next time
-                                queue.push(Range { start: conversion.source.end + 1, end });
-                            }
-                            found_match = true;
-                            break;
-                        }
-                    }
-
-                    if !found_match {
-                        // No conversion found, just map the range directly
-                        result.push(*range);
-                    }
-                 }
-                None => { break; }
+    // Overlapping source ranges leave some values with two conversions, which is an error.
+    // Overlapping destinations give some values two sources, and values in a gap between source
+    // ranges map to themselves as the puzzle says, so those are only warnings.
+    fn validate(&self, validation: &mut Validation) {
+        let mut by_dest : Vec<&MapConversion> = self.conversions.iter().collect();
+        by_dest.sort_by_key(|c| c.dest.start);
+
+        for (kind, ranges) in [
+            ("source", self.conversions.iter().map(|c| c.source).collect::<Vec<Range>>()),
+            ("destination", by_dest.iter().map(|c| c.dest).collect()),
+        ] {
+            for pair in ranges.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                if b.start <= a.end {
+                    let found = if kind == "source" { &mut validation.errors } else { &mut validation.warnings };
+                    found.push(format!("{}: {kind} ranges {}-{} and {}-{} overlap", self.name, a.start, a.end, b.start, b.end));
+                } else if kind == "source" && b.start > a.end + 1 {
+                    validation.warnings.push(format!("{}: values {}-{} between source ranges map to themselves", self.name, a.end + 1, b.start - 1));
+                }
             }
         }
-
-        result
     }
 
-    fn convert_range(&self, range: &Range) -> Range {
-        self.conversions.iter()
-            .find(|conv| conv.source.contains_range(range))
-            .map(|conv| conv.convert_range(range))
-            .unwrap_or(*range)
-    }
-
-    fn map_values(&self, value: &Vec<Range>) -> Vec<Range> {
-        value.iter()
-            .map(|range| self.split_ranges(&range))
-            .flatten()
-            .map(|range| self.convert_range(&range))
-            .collect()
+    fn to_piecewise(&self) -> PiecewiseMap {
+        PiecewiseMap::from_shifts(self.conversions.iter().map(|c| (c.source, c.dest.start - c.source.start)))
     }
 }
 
@@ -110,18 +67,40 @@ struct Almanac {
     maps: Vec<AlmanacMap>
 }
 impl Almanac {
-    fn get_lowest_seed_location(self: &Self) -> i64 {
-        let mut locations = self.seeds.to_vec();
-        
+    // Every map composed into one, straight from seed to location
+    fn seed_to_location(self: &Self) -> PiecewiseMap {
+        self.maps.iter().fold(PiecewiseMap::identity(), |map, next| map.then(&next.to_piecewise()))
+    }
+
+    fn validate(self: &Self) -> Validation {
+        let mut validation = Validation::default();
         for map in &self.maps {
-            locations = map.map_values(&locations);
+            map.validate(&mut validation);
         }
+        validation
+    }
 
-        locations.sort_by(|a, b| a.start.cmp(&b.start));
+    // The almanac's seeds that end up somewhere in the locations
+    fn seeds_for_locations(self: &Self, locations: &Range) -> Vec<Range> {
+        let mut seeds : Vec<Range> = self.seed_to_location().preimage(locations).iter()
+            .flat_map(|found| self.seeds.iter().filter_map(|seed| {
+                let start = seed.start.max(found.start);
+                let end = seed.end.min(found.end);
+                (start <= end).then_some(Range { start, end })
+            }))
+            .collect();
+        seeds.sort_by_key(|r| r.start);
+        seeds
+    }
 
-        assert!(!locations.is_empty());
+    // The lowest of the almanac's seeds that ends up at the location, if any does
+    fn seed_reaching(self: &Self, location: i64) -> Option<i64> {
+        self.seeds_for_locations(&Range { start: location, end: location }).first().map(|r| r.start)
+    }
 
-        locations[0].start
+    fn get_lowest_seed_location(self: &Self) -> i64 {
+        let seed_to_location = aoc_runner::timed("compose", || self.seed_to_location());
+        seed_to_location.min_over(&self.seeds).expect("No seeds")
     }
 }
 
@@ -179,8 +158,9 @@ fn parse_map_ranges(input: &mut Lines) -> Vec<MapConversion> {
     map
 }
 
-fn parse_map(input: &mut Lines, almanac: &mut Almanac) {
-    let map = AlmanacMap { conversions: parse_map_ranges(input) };
+fn parse_map(input: &mut Lines, name: &str, almanac: &mut Almanac) {
+    let name = name.trim_end_matches(':').trim_end_matches(" map").to_string();
+    let map = AlmanacMap { name, conversions: parse_map_ranges(input) };
     almanac.maps.push(map);
 }
 
@@ -188,7 +168,7 @@ fn try_parse_map(input: &mut Lines, almanac: &mut Almanac) -> bool {
     let now = SystemTime::now();
     let map_header = input.next();
     match map_header {
-        Some(_) => { parse_map(input, almanac); println!("Map parse took {} ms ", (now.elapsed().unwrap().as_micros() as f32 / 1000.0)); return true; },
+        Some(header) => { parse_map(input, header, almanac); println!("Map parse took {} ms ", (now.elapsed().unwrap().as_micros() as f32 / 1000.0)); return true; },
         None => { return false; }
     }
 }
@@ -209,7 +189,7 @@ fn parse_almanac(input: &mut Lines, use_seed_ranges: bool) -> Almanac {
     almanac
 }
 
-fn run_test(input: &str, use_seed_range: bool) {
+fn run_test(input: &str, use_seed_range: bool) -> Answer {
     let mut lines = input.lines();    
 
     let now = SystemTime::now();        
@@ -218,12 +198,180 @@ fn run_test(input: &str, use_seed_range: bool) {
 
     let now = SystemTime::now();
     let lowest_loc = almanac.get_lowest_seed_location();
-    println!("Lowest Location (range={use_seed_range}): {lowest_loc} | time spent: {} ms", (now.elapsed().unwrap().as_micros() as f32 / 1000.0));   
+    println!("Found lowest location (range={use_seed_range}) in {} ms", (now.elapsed().unwrap().as_micros() as f32 / 1000.0));
+
+    lowest_loc.into()
+}
+
+// Prints what is wrong with the almanac's maps, and false if they can't be solved
+fn check_almanac(input: &str) -> bool {
+    let validation = parse_almanac(&mut input.lines(), false).validate();
+    for warning in &validation.warnings {
+        println!("Warning: {warning}");
+    }
+    for error in &validation.errors {
+        println!("Invalid almanac: {error}");
+    }
+    validation.errors.is_empty()
 }
 
+// `<value>` or `<start>-<end>`
+fn parse_query(text: &str) -> Range {
+    let number = |n: &str| n.parse::<i64>().unwrap_or_else(|_| panic!("Invalid value '{n}'"));
+    match text.split_once('-') {
+        Some((start, end)) => Range { start: number(start), end: number(end) },
+        None => Range { start: number(text), end: number(text) },
+    }
+}
+
+aoc_runner::install_counting_allocator!();
+
 fn main() {
     let input = include_str!("input.txt");
-    
-    run_test(input, false);
-    run_test(input, true);
+    if let Some(query) = aoc_runner::arg_value("--locate") {
+        let map = parse_almanac(&mut input.lines(), false).seed_to_location();
+        let seeds = parse_query(&query);
+        if seeds.start == seeds.end {
+            println!("Seed {} -> location {}", seeds.start, map.apply(seeds.start));
+        } else {
+            let locations : Vec<String> = map.apply_range(&seeds).iter().map(|r| format!("{}-{}", r.start, r.end)).collect();
+            println!("Seeds {query} -> locations {}", locations.join(", "));
+        }
+        return;
+    }
+    if std::env::args().any(|a| a == "--print-map") {
+        let map = parse_almanac(&mut input.lines(), false).seed_to_location();
+        println!("Seed to location, {} segments:", map.segments().len());
+        print!("{map}");
+        return;
+    }
+
+    // `--seeds-for <location or range>` lists the seed ranges ending up there, and
+    // `--reachable <location>` says whether any seed range does
+    if let Some(query) = aoc_runner::arg_value("--seeds-for") {
+        let seeds : Vec<String> = parse_almanac(&mut input.lines(), true).seeds_for_locations(&parse_query(&query)).iter()
+            .map(|r| format!("{}-{}", r.start, r.end))
+            .collect();
+        println!("Locations {query} <- seeds {}", if seeds.is_empty() { String::from("none") } else { seeds.join(", ") });
+        return;
+    }
+    if let Some(location) = aoc_runner::arg_value("--reachable") {
+        let location = parse_query(&location).start;
+        match parse_almanac(&mut input.lines(), true).seed_reaching(location) {
+            Some(seed) => println!("Location {location} is reached from seed {seed}"),
+            None => println!("Location {location} is not reachable from any seed range"),
+        }
+        return;
+    }
+
+    if !check_almanac(input) {
+        return;
+    }
+    aoc_runner::answer!(1, run_test(input, false));
+    aoc_runner::answer!(2, run_test(input, true));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE : &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn composed_map() {
+        let almanac = parse_almanac(&mut SAMPLE.lines(), false);
+        let map = almanac.seed_to_location();
+
+        let locations : Vec<i64> = [79, 14, 55, 13].iter().map(|&seed| map.apply(seed)).collect();
+        assert_eq!(locations, vec![82, 43, 86, 35]);
+        assert_eq!(almanac.get_lowest_seed_location(), 35);
+
+        // Each value checked against the maps applied one at a time
+        for seed in 0..120 {
+            let stepwise = almanac.maps.iter().fold(seed, |value, m| m.to_piecewise().apply(value));
+            assert_eq!(map.apply(seed), stepwise, "seed {seed}");
+        }
+
+        let ranges = parse_almanac(&mut SAMPLE.lines(), true);
+        assert_eq!(ranges.get_lowest_seed_location(), 46);
+        assert_eq!(map.apply_range(&Range { start: 79, end: 92 }).iter().map(|r| r.start).min(), Some(46));
+        assert!(map.segments().windows(2).all(|w| w[0].end + 1 == w[1].start && w[0].offset != w[1].offset));
+        let printed = map.to_string();
+        assert!(printed.starts_with("           0 - 13           ->           22 - 35           (+22)\n"));
+        assert!(printed.ends_with("         100 - ..           ->          100 - ..           (+0)\n"));
+    }
+
+    #[test]
+    fn inverse_queries() {
+        let almanac = parse_almanac(&mut SAMPLE.lines(), true);
+        let map = almanac.seed_to_location();
+
+        // Location 0 only comes from seed 70, outside the seed ranges
+        assert_eq!(map.preimage(&Range { start: 0, end: 0 }), vec![Range { start: 70, end: 70 }]);
+        assert_eq!(almanac.seed_reaching(0), None);
+        assert_eq!(almanac.seed_reaching(46), Some(82));
+        assert_eq!(almanac.seeds_for_locations(&Range { start: 0, end: 59 }),
+            vec![Range { start: 62, end: 65 }, Range { start: 82, end: 91 }]);
+
+        for location in 0..120 {
+            for seed in map.preimage(&Range { start: location, end: location }) {
+                assert_eq!(map.apply(seed.start), location);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_maps() {
+        assert_eq!(parse_almanac(&mut SAMPLE.lines(), false).validate(), Validation::default());
+
+        let input = "seeds: 1 2\n\na-to-b map:\n10 0 5\n20 3 5\n30 10 2\n\nb-to-c map:\n0 10 5\n2 20 5\n";
+        let validation = parse_almanac(&mut input.lines(), false).validate();
+        assert_eq!(validation.errors, vec!["a-to-b: source ranges 0-4 and 3-7 overlap"]);
+        assert_eq!(validation.warnings, vec![
+            "a-to-b: values 8-9 between source ranges map to themselves",
+            "b-to-c: values 15-19 between source ranges map to themselves",
+            "b-to-c: destination ranges 0-4 and 2-6 overlap",
+        ]);
+
+        // A gap is only a warning, and seeds in it keep their value
+        let input = "seeds: 8 1\n\na-to-b map:\n10 0 5\n30 10 2\n";
+        let almanac = parse_almanac(&mut input.lines(), false);
+        assert!(almanac.validate().errors.is_empty());
+        assert_eq!(almanac.get_lowest_seed_location(), 8);
+        assert!(check_almanac(input));
+    }
 }
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use crate::Range;
+
+// Values from `start` to `end` (inclusive) map to themselves plus `offset`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: i64,
+    pub end: i64,
+    pub offset: i64,
+}
+
+// A function over every value from 0 up, made of segments that each shift their values by an
+// offset. Kept normalized: segments are sorted, cover the whole domain without overlapping, and
+// neighbouring segments never share an offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    segments: Vec<Segment>,
+}
+impl PiecewiseMap {
+    pub fn identity() -> Self {
+        PiecewiseMap { segments: vec![Segment { start: 0, end: i64::MAX, offset: 0 }] }
+    }
+
+    // Shifted ranges as (source, offset), sorted by source. Everything between them maps to
+    // itself, and a range overlapping an earlier one only keeps the part left uncovered.
+    pub fn from_shifts(shifts: impl IntoIterator<Item = (Range, i64)>) -> Self {
+        let mut segments = Vec::new();
+        let mut next = 0;
+        for (mut source, offset) in shifts {
+            if source.end < next {
+                continue;
+            }
+            source.start = source.start.max(next);
+            if source.start > next {
+                segments.push(Segment { start: next, end: source.start - 1, offset: 0 });
+            }
+            segments.push(Segment { start: source.start, end: source.end, offset });
+            next = source.end + 1;
+        }
+        segments.push(Segment { start: next, end: i64::MAX, offset: 0 });
+        PiecewiseMap::normalized(segments)
+    }
+
+    fn normalized(segments: Vec<Segment>) -> Self {
+        let mut merged : Vec<Segment> = Vec::with_capacity(segments.len());
+        for segment in segments {
+            match merged.last_mut() {
+                Some(last) if last.offset == segment.offset && last.end + 1 == segment.start => last.end = segment.end,
+                _ => merged.push(segment),
+            }
+        }
+        PiecewiseMap { segments: merged }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    // Index of the segment holding value, by binary search
+    fn segment_index(&self, value: i64) -> usize {
+        self.segments.partition_point(|s| s.end < value)
+    }
+
+    pub fn apply(&self, value: i64) -> i64 {
+        value + self.segments[self.segment_index(value)].offset
+    }
+
+    // The pieces a range maps to, in the order of the values they came from
+    pub fn apply_range(&self, range: &Range) -> Vec<Range> {
+        self.pieces(range).map(|(piece, offset)| Range { start: piece.start + offset, end: piece.end + offset }).collect()
+    }
+
+    // The range cut at every segment boundary inside it, with each piece's offset
+    fn pieces<'a>(&'a self, range: &Range) -> impl Iterator<Item = (Range, i64)> + 'a {
+        let range = *range;
+        self.segments[self.segment_index(range.start)..].iter()
+            .take_while(move |s| s.start <= range.end)
+            .map(move |s| (Range { start: s.start.max(range.start), end: s.end.min(range.end) }, s.offset))
+    }
+
+    // This map followed by `next`, as a single map
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let mut segments = Vec::new();
+        for segment in &self.segments {
+            // The values this segment lands on, split wherever next's segments change
+            let image = Range { start: segment.start + segment.offset, end: segment.end.saturating_add(segment.offset) };
+            for (piece, offset) in next.pieces(&image) {
+                segments.push(Segment {
+                    start: piece.start - segment.offset,
+                    end: piece.end - segment.offset,
+                    offset: segment.offset + offset,
+                });
+            }
+        }
+        PiecewiseMap::normalized(segments)
+    }
+
+    // Every value mapping into the range, sorted and merged. Several segments can land on the
+    // same values, so a single value can have more than one.
+    pub fn preimage(&self, range: &Range) -> Vec<Range> {
+        let pieces = self.segments.iter().filter_map(|s| {
+            let start = (s.start + s.offset).max(range.start);
+            let end = s.end.saturating_add(s.offset).min(range.end);
+            (start <= end).then(|| Range { start: start - s.offset, end: end - s.offset })
+        });
+
+        let mut merged : Vec<Range> = Vec::new();
+        for piece in pieces {
+            match merged.last_mut() {
+                Some(last) if last.end + 1 == piece.start => last.end = piece.end,
+                _ => merged.push(piece),
+            }
+        }
+        merged
+    }
+
+    // Within one segment the values only go up, so the lowest value over a range is at its start
+    // or at the start of a segment inside it. Those are the only values checked.
+    pub fn min_over(&self, ranges: &[Range]) -> Option<i64> {
+        ranges.iter()
+            .flat_map(|range| self.pieces(range))
+            .map(|(piece, offset)| piece.start + offset)
+            .min()
+    }
+}
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for s in &self.segments {
+            let end = if s.end == i64::MAX { String::from("..") } else { s.end.to_string() };
+            let mapped_end = if s.end == i64::MAX { String::from("..") } else { (s.end + s.offset).to_string() };
+            writeln!(f, "{:>12} - {:<12} -> {:>12} - {:<12} ({:+})", s.start, end, s.start + s.offset, mapped_end, s.offset)?;
+        }
+        Ok(())
+    }
+}
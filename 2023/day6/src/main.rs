@@ -1,27 +1,33 @@
-use std::{cmp::max, time::SystemTime};
+mod race;
 
-struct RaceResult {
-    time: i64,
-    distance: i64,
+use std::{cmp::max, fmt::Display, str::FromStr, time::SystemTime};
+
+use aoc_runner::Answer;
+use num::{BigUint, ToPrimitive};
+use race::{HoldInterval, RaceNumber};
+
+struct RaceResult<T> {
+    time: T,
+    distance: T,
 }
 
-fn get_distance_traveled(time_allowed: i64, time_held: i64) -> i64 {
-    let speed_per_sec = 1;
-    let speed = speed_per_sec * time_held;
+// u128, as a large --acceleration times a time held near a billion ms squared overflows an i64
+fn get_distance_traveled(time_allowed: u128, time_held: u128, acceleration: u128) -> u128 {
+    let speed = acceleration * time_held;
 
-    let time_moving = max(time_allowed - time_held, 0);
+    let time_moving = max(time_allowed, time_held) - time_held;
     time_moving * speed
 }
 
-fn get_values_2(line: &str) -> Vec<i64> {
+fn get_values_2<T: FromStr>(line: &str) -> Vec<T> {
     line.split_once(':').unwrap().1
         .trim().split(' ').into_iter()
         .filter(|v| !v.is_empty())
-        .map(|v| v.parse::<i64>().unwrap())
+        .map(|v| v.parse::<T>().ok().unwrap())
         .collect()
 }
 
-fn get_values(line: &str, remove_spaces: bool) -> Vec<i64> {
+fn get_values<T: FromStr>(line: &str, remove_spaces: bool) -> Vec<T> {
     if remove_spaces {
         let without_spaces = line.replace(" ", "");
         get_values_2(&without_spaces)
@@ -30,94 +36,97 @@ fn get_values(line: &str, remove_spaces: bool) -> Vec<i64> {
     }
 }
 
-fn parse_results(input: &str, remove_spaces: bool) -> Vec<RaceResult> {
+fn parse_results<T: FromStr>(input: &str, remove_spaces: bool) -> Vec<RaceResult<T>> {
     let lines : Vec<&str> = input.lines().collect();
     let times = get_values(lines[0], remove_spaces);
     let distances = get_values(lines[1], remove_spaces);
 
-    let mut result = Vec::new();
-    result.reserve(times.len());
-
-    for i in 0..times.len() {
-        result.push(RaceResult { time: times[i], distance: distances[i] });
-    }
-
-    result
+    times.into_iter().zip(distances)
+        .map(|(time, distance)| RaceResult { time, distance })
+        .collect()
 }
 
-fn get_ways_to_beat(result: &RaceResult) -> usize {
-    (1..result.time).map(|time| get_distance_traveled(result.time, time))
+// Tries every hold, kept as an oracle for the exact solver
+fn get_ways_to_beat(result: &RaceResult<u128>, acceleration: u128) -> usize {
+    (1..result.time).map(|time| get_distance_traveled(result.time, time, acceleration))
         .filter(|time| time > &result.distance)
         .count()
 }
 
-fn get_num_ways_to_beat(results: &Vec<RaceResult>, use_quadratic: bool) -> i64 {
-    if use_quadratic {
-        results.iter()
-            .map(|r| solve_quadratic(r.time, r.distance))
-            .filter(|r| r.is_some())
-            .map(|r| {
-                let (a, b) = r.unwrap();
-                (b - a) + 1
-            })
-            .reduce(|a, b| a * b)
-            .unwrap_or(0) as i64
-    } else {
-        results.iter()
-            .map(|result| get_ways_to_beat(result))
-            .reduce(|a, b| a * b)
-            .unwrap_or(0) as i64
+fn get_num_ways_to_beat<T: RaceNumber + Display>(results: &[RaceResult<T>], acceleration: &T) -> T {
+    let intervals : Vec<Option<HoldInterval<T>>> = results.iter()
+        .map(|r| race::solve(&r.time, &r.distance, acceleration))
+        .collect();
+
+    for (i, interval) in intervals.iter().enumerate() {
+        match interval {
+            Some(interval) => println!("Race {}: hold {}-{} ms, {} ways", i + 1, interval.first, interval.last, interval.count()),
+            None => println!("Race {}: the record can't be beaten", i + 1),
+        }
     }
+
+    intervals.iter()
+        .map(|interval| interval.as_ref().map_or(T::from(0), |i| i.count()))
+        .fold(T::from(1), |a, b| a * b)
 }
 
-fn solve_quadratic(max_time: i64, best_distance: i64) -> Option<(i64, i64)> {
-    // equation = x^2 - x(max_time) + best_distance
-    // a = 1, b = max_time, c = best_distance
-    let a = 1.0;
-    let b = -max_time as f32;
-    let c = (best_distance + 1) as f32; // add one because we need to beat not match!
-
-    let root = b*b - 4.0*a*c;
-    if root > 0.0 {
-        let root = root.sqrt();
-        let min = (-b - root) / (2.0 * a);
-        let max = (-b + root) / (2.0 * a);
-        Some((min.ceil() as i64, max.floor() as i64))
-    } else {
-        None
-    }
+// u128 is plenty unless a*a*t*t could overflow it, which only a generated race would need
+fn fits_u128(results: &[RaceResult<BigUint>], acceleration: &BigUint) -> bool {
+    results.iter().all(|r| 2 * (acceleration.bits() + r.time.bits()) + 3 < 128 && r.distance.bits() < 120)
 }
 
-fn part_1(input: &str) {
+fn solve(input: &str, remove_spaces: bool, acceleration: u64) -> Answer {
     let now = SystemTime::now();        
-    let results = parse_results(input, false);
+    let results = parse_results::<BigUint>(input, remove_spaces);
     println!("Finished parsing results in {} ms", (now.elapsed().unwrap().as_nanos() as f32 / 1000000.0));
 
-    let now = SystemTime::now();        
-    let num_ways_to_beat = get_num_ways_to_beat(&results, false);
-    println!("Part 1: {num_ways_to_beat} | took {} ms (Brute Force)", (now.elapsed().unwrap().as_nanos() as f32 / 1000000.0));
+    // Only races short enough to try every hold, where acceleration * time * time fits in a u128
+    let small : Option<Vec<RaceResult<u128>>> = results.iter()
+        .map(|r| Some(RaceResult { time: r.time.to_u128()?, distance: r.distance.to_u128()? }))
+        .collect();
+    let brute_force : Option<Answer> = small.filter(|s| s.iter().all(|r| r.time <= 1_000_000_000)).map(|small| {
+        let now = SystemTime::now();        
+        let num_ways_to_beat = small.iter()
+            .map(|result| BigUint::from(get_ways_to_beat(result, acceleration as u128)))
+            .product::<BigUint>();
+        println!("Brute Force: {num_ways_to_beat} | took {} ms", (now.elapsed().unwrap().as_nanos() as f32 / 1000000.0));
+        num_ways_to_beat.into()
+    });
 
     let now = SystemTime::now();        
-    let num_ways_to_beat = get_num_ways_to_beat(&results, true);
-    println!("Part 1: {num_ways_to_beat} | took {} ms (Quadratic)", (now.elapsed().unwrap().as_nanos() as f32 / 1000000.0));
+    let big_acceleration = BigUint::from(acceleration);
+    let num_ways_to_beat : Answer = if fits_u128(&results, &big_acceleration) {
+        let results : Vec<RaceResult<u128>> = results.iter()
+            .map(|r| RaceResult { time: r.time.to_u128().unwrap(), distance: r.distance.to_u128().unwrap() })
+            .collect();
+        get_num_ways_to_beat(&results, &(acceleration as u128)).into()
+    } else {
+        get_num_ways_to_beat(&results, &big_acceleration).into()
+    };
+    println!("Exact: {num_ways_to_beat} | took {} ms", (now.elapsed().unwrap().as_nanos() as f32 / 1000000.0));
+    if let Some(brute_force) = brute_force {
+        assert_eq!(brute_force, num_ways_to_beat, "the exact solver disagrees with trying every hold");
+    }
+
+    num_ways_to_beat
 }
 
-fn part_2(input: &str) {
-    let now = SystemTime::now();        
-    let results = parse_results(input, true);
-    println!("Finished parsing results in {} ms", (now.elapsed().unwrap().as_nanos() as f32 / 1000000.0));
-    
-    let now = SystemTime::now();        
-    let num_ways_to_beat = get_num_ways_to_beat(&results, false);
-    println!("Part 2: {num_ways_to_beat} | took {} ms (Brute Force)", (now.elapsed().unwrap().as_nanos() as f32 / 1000000.0));
+fn part_1(input: &str, acceleration: u64) -> Answer {
+    solve(input, false, acceleration)
+}
 
-    let now = SystemTime::now();        
-    let num_ways_to_beat = get_num_ways_to_beat(&results, true);
-    println!("Part 2: {num_ways_to_beat} | took {} ms (Quadratic)", (now.elapsed().unwrap().as_nanos() as f32 / 1000000.0));
+fn part_2(input: &str, acceleration: u64) -> Answer {
+    solve(input, true, acceleration)
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
     let input = include_str!("input.txt");
-    part_1(&input);
-    part_2(&input);
+    // `--acceleration <n>` gives the boat n mm/ms of speed per ms held, instead of 1
+    let acceleration = aoc_runner::arg_value("--acceleration")
+        .map_or(1, |a| a.parse().expect("--acceleration needs a whole number"));
+
+    aoc_runner::answer!(1, part_1(&input, acceleration));
+    aoc_runner::answer!(2, part_2(&input, acceleration));
 }
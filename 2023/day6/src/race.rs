@@ -0,0 +1,103 @@
+use num::{integer::Roots, Integer};
+
+// Any unsigned integer type wide enough for the race: u64, u128 or BigUint
+pub trait RaceNumber: Integer + Roots + Clone + From<u8> {}
+impl<T: Integer + Roots + Clone + From<u8>> RaceNumber for T {}
+
+// Every button hold, in ms, that beats the record, from `first` to `last` inclusive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoldInterval<T> {
+    pub first: T,
+    pub last: T,
+}
+impl<T: RaceNumber> HoldInterval<T> {
+    pub fn count(&self) -> T {
+        self.last.clone() - self.first.clone() + T::from(1)
+    }
+}
+
+// Holding for `held` ms builds up `acceleration` mm/ms of speed for each ms held
+pub fn distance<T: RaceNumber>(time: &T, held: &T, acceleration: &T) -> T {
+    if held > time {
+        return T::zero();
+    }
+    acceleration.clone() * held.clone() * (time.clone() - held.clone())
+}
+
+// The distance is a downward parabola in the time held, symmetric around time / 2. Its lower
+// root comes from an integer square root, which is exact apart from rounding down, and is then
+// nudged onto the first hold that actually wins. The last is its mirror image.
+pub fn solve<T: RaceNumber>(time: &T, record: &T, acceleration: &T) -> Option<HoldInterval<T>> {
+    let two = T::from(2);
+    let beats = |held: &T| distance(time, held, acceleration) > *record;
+
+    let middle = time.clone() / two.clone();
+    if !beats(&middle) {
+        return None;
+    }
+
+    // Beating the middle hold means a*t*t > 4*d, so this can't underflow
+    let a = acceleration.clone();
+    let discriminant = a.clone() * a.clone() * time.clone() * time.clone() - T::from(4) * a.clone() * record.clone();
+    let root = discriminant.sqrt();
+    let at = a.clone() * time.clone();
+    let mut first = if root >= at { T::zero() } else { (at - root) / (two * a) };
+
+    while !beats(&first) {
+        first = first + T::from(1);
+    }
+    while first > T::zero() && beats(&(first.clone() - T::from(1))) {
+        first = first - T::from(1);
+    }
+
+    let last = time.clone() - first.clone();
+    Some(HoldInterval { first, last })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigUint;
+
+    #[test]
+    fn sample_races() {
+        let intervals : Vec<Option<HoldInterval<u64>>> = [(7, 9), (15, 40), (30, 200)].iter()
+            .map(|(time, record)| solve(time, record, &1))
+            .collect();
+        assert_eq!(intervals, vec![
+            Some(HoldInterval { first: 2, last: 5 }),
+            Some(HoldInterval { first: 4, last: 11 }),
+            Some(HoldInterval { first: 11, last: 19 }),
+        ]);
+        assert_eq!(solve(&71530u128, &940200, &1).unwrap().count(), 71503);
+        assert_eq!(solve(&7u64, &12, &1), None);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for acceleration in 1..4u64 {
+            for time in 0..40u64 {
+                for record in 0..200u64 {
+                    let wins : Vec<u64> = (0..=time).filter(|held| distance(&time, held, &acceleration) > record).collect();
+                    let expected = wins.first().map(|&first| HoldInterval { first, last: *wins.last().unwrap() });
+                    assert_eq!(solve(&time, &record, &acceleration), expected, "time {time}, record {record}, acceleration {acceleration}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn big_races() {
+        // Far past where f64 can tell neighbouring holds apart
+        let time : BigUint = "100000000000000000000000000000007".parse().unwrap();
+        let record : BigUint = "2499999999999999999999999999999999999999999999999999999999999999".parse().unwrap();
+        let acceleration = BigUint::from(3u8);
+
+        let interval = solve(&time, &record, &acceleration).unwrap();
+        let one = BigUint::from(1u8);
+        assert!(distance(&time, &interval.first, &acceleration) > record);
+        assert!(distance(&time, &(interval.first.clone() - one.clone()), &acceleration) <= record);
+        assert!(distance(&time, &(interval.last.clone() + one), &acceleration) <= record);
+        assert_eq!(interval.count(), time.clone() + BigUint::from(1u8) - interval.first.clone() * BigUint::from(2u8));
+    }
+}
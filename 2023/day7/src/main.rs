@@ -1,121 +1,100 @@
-use std::collections::HashMap;
-use std::cmp::Ordering;
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum HandType {
-    HighCard,
-    OnePair,
-    TwoPair,
-    ThreeOfKind,
-    FullHouse,
-    FourOfKind,
-    FiveOfKind,
-}
-impl HandType {
-    fn from_cards(cards: &str, j_is_wildcard: bool) -> HandType {
-        let mut card_counts : HashMap<char, i32> = HashMap::new();
-        for char in cards.chars() {
-            let count = card_counts.entry(char).or_insert(0);
-            *count = *count + 1;
-        }
-
-        if j_is_wildcard {
-            // Replace Js with the most-common card
-            let highest_non_j = card_counts.iter()
-                .filter(|(k, _)| *k != &'J')
-                .max_by(|a, b| a.1.cmp(b.1))
-                .map(|(k, _)| k);
-
-            match highest_non_j {
-                Some(card) => {
-                    let j_count = *card_counts.get(&'J').unwrap_or(&0);
-                    card_counts.entry(*card).and_modify(|v| *v += j_count);
-
-                    card_counts.remove(&'J');
-                },
-                None => {}
-            }
-        }
-
-        let highest_card_count = card_counts.iter()
-            .map(|(_, value)| value)
-            .max().unwrap();
-
-        match card_counts.len() {
-            1 => { assert_eq!(highest_card_count, &5); HandType::FiveOfKind },
-            2 => if highest_card_count == &4 { HandType::FourOfKind } else { HandType::FullHouse }
-            3 => if highest_card_count == &3 { HandType::ThreeOfKind } else { HandType::TwoPair }
-            4 => HandType::OnePair,
-            _ => HandType::HighCard
-        }
+mod report;
+mod rules;
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+use aoc_runner::{json, Answer, Explained, Value};
+use report::Report;
+use rules::{Hand, HandType, RuleSet};
+
+// The puzzle's example hands, for the rules and report tests
+#[cfg(test)]
+const SAMPLE : &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+// Prints the ranking with `--report table` or `--report csv`
+fn find_winnings(input: &str, rules: &RuleSet) -> Explained<Answer> {
+    let report = aoc_runner::timed("rank", || Report::new(input, rules));
+    match aoc_runner::arg_value("--report").as_deref() {
+        Some("table") => print!("{}", report.to_table()),
+        Some("csv") => print!("{}", report.to_csv()),
+        Some(format) => panic!("Unknown report format '{format}', expected table or csv"),
+        None => {}
     }
-}
 
-#[derive(Debug)]
-struct Hand {
-    bid: i32,
-    card_strengths: Vec<i32>,
-    hand_type: HandType,
+    let ranked_hands : Vec<Value> = report.hands.iter()
+        .map(|hand| json!({
+            "rank": hand.rank,
+            "cards": hand.cards,
+            "type": hand.hand_type.to_string(),
+            "substituted": hand.substituted,
+            "bid": hand.bid,
+            "winnings": hand.winnings,
+        }))
+        .collect();
+
+    Explained::new(report.total_winnings().into()).with("ranked_hands", &ranked_hands)
 }
-impl Hand {
-    fn new(line: &str, j_is_wildcard: bool) -> Hand {
-        let line_parts : Vec<&str> = line.split(' ').collect();
-        let cards = line_parts[0];
-        Hand {
-            bid: line_parts[1].parse::<i32>().unwrap(),
-            card_strengths: get_card_strengths(cards, j_is_wildcard),
-            hand_type: HandType::from_cards(cards, j_is_wildcard),
-        }
+
+// Streaming version for inputs too large to hold every hand. Hands are only compared by type then
+// card strengths, so hands are grouped by that key (at most 7 * 13^5 of them under the puzzle's
+// rules) in sorted order. Within a group hands keep their input order, as the stable sort does, so
+// each group tracks its bids and the bids weighted by position inside the group.
+fn find_winnings_reader<R: BufRead>(reader: R, rules: &RuleSet) -> Answer {
+    let mut groups : BTreeMap<(HandType, Vec<usize>), (i64, i64, i64)> = BTreeMap::new();
+    aoc_runner::for_each_line(reader, |line| {
+        let hand = Hand::parse(line, rules);
+        let (count, bids, weighted_bids) = groups.entry((hand.hand_type, hand.strengths)).or_insert((0, 0, 0));
+        *weighted_bids += hand.bid as i64 * *count;
+        *bids += hand.bid as i64;
+        *count += 1;
+    });
+
+    let mut rank = 1;
+    let mut sum = 0;
+    for (count, bids, weighted_bids) in groups.values() {
+        sum += bids * rank + weighted_bids;
+        rank += count;
     }
+    sum.into()
 }
 
-fn get_card_strengths(cards: &str, j_is_wildcard: bool) -> Vec<i32> {
-    const CARD_STRENGTHS : &'static [char] = &['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A'];
-    const CARD_STRENGTHS_J_WC : &'static [char] = &['J', '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'Q', 'K', 'A'];
-
-    let strengths = if j_is_wildcard { CARD_STRENGTHS_J_WC } else { CARD_STRENGTHS };
-
-    cards.chars()
-        .map(|card| strengths.iter().position(|x| x == &card).unwrap() as i32)
-        .collect()
+// `--rules <file>` also plays the hands under a rule set loaded from the file (see RuleSet::parse)
+fn custom_rules() -> Option<RuleSet> {
+    let path = aoc_runner::arg_value("--rules")?;
+    let config = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {path}: {e}"));
+    Some(RuleSet::parse(&config).unwrap_or_else(|e| panic!("Invalid rules in {path}: {e}")))
 }
 
-// Returns true if 'a' should be sorted before 'b'
-fn compare_hands(a: &Hand, b: &Hand) -> Ordering {    
-    match a.hand_type.cmp(&b.hand_type) {
-        Ordering::Greater => Ordering::Greater,
-        Ordering::Less => Ordering::Less,
-        Ordering::Equal => {
-            let first_difference = a.card_strengths.iter().enumerate()
-                .map(|(i, strength)| strength.cmp(&b.card_strengths[i]))
-                .find(|x| x != &Ordering::Equal);
+aoc_runner::install_counting_allocator!();
 
-            match first_difference {
-                Some(x) => x,
-                None => Ordering::Equal
-            }
+fn main() {
+    let custom = custom_rules();
+    if let Some(path) = aoc_runner::input_path() {
+        aoc_runner::answer!(1, find_winnings_reader(aoc_runner::open_input(&path), &RuleSet::standard()));
+        aoc_runner::answer!(2, find_winnings_reader(aoc_runner::open_input(&path), &RuleSet::jokers()));
+        if let Some(rules) = custom {
+            println!("Rules '{}': {}", rules.name, find_winnings_reader(aoc_runner::open_input(&path), &rules));
         }
+        return;
     }
-}
-
-fn parse_hands(input: &str, j_is_wildcard: bool) -> Vec<Hand> {
-    input.lines()
-        .map(|line| Hand::new(line, j_is_wildcard))
-        .collect()
-}
-
-fn find_winnings(input: &str, j_is_wildcard: bool) {
-    let mut hands = parse_hands(input, j_is_wildcard);
-    hands.sort_by(compare_hands);
 
-    let sum: i32 = hands.iter().enumerate()
-        .map(|(i, hand)| hand.bid * (i as i32 + 1))
-        .sum();
-    println!("Total Winnings (J_WC={j_is_wildcard}): {sum}");
-}
-
-fn main() {
     let input = include_str!("input.txt");    
-    find_winnings(input, false);
-    find_winnings(input, true);
+    aoc_runner::explain!(1, find_winnings(input, &RuleSet::standard()));
+    aoc_runner::explain!(2, find_winnings(input, &RuleSet::jokers()));
+    if let Some(rules) = &custom {
+        println!("Rules '{}': {}", rules.name, find_winnings(input, rules).result);
+    }
+
+    // `--diff` shows which hands move between the puzzle's two rule sets, or from the standard
+    // rules to the --rules ones
+    if std::env::args().any(|a| a == "--diff") {
+        let after = custom.unwrap_or_else(RuleSet::jokers);
+        print!("{}", report::diff_table(&Report::new(input, &RuleSet::standard()), &Report::new(input, &after)));
+    }
 }
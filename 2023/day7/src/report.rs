@@ -0,0 +1,152 @@
+use std::{collections::HashMap, fmt::Write};
+
+use crate::rules::{Hand, HandType, RuleSet};
+
+// One hand's place in the ranking. `line` is where it was in the input, which tells hands with
+// the same cards apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedHand {
+    pub line: usize,
+    pub rank: usize,
+    pub cards: String,
+    pub hand_type: HandType,
+    // The cards with the best substitution for any wild cards, if there were some
+    pub substituted: Option<String>,
+    pub bid: i32,
+    pub winnings: i64,
+}
+
+// Every hand ranked under one rule set, weakest first
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub rules: String,
+    pub hands: Vec<RankedHand>,
+}
+impl Report {
+    pub fn new(input: &str, rules: &RuleSet) -> Report {
+        let mut hands : Vec<(usize, Hand)> = input.lines().enumerate()
+            .map(|(line, l)| (line, Hand::parse(l, rules)))
+            .collect();
+        // Stable, so equal hands keep their input order
+        hands.sort_by(|a, b| a.1.cmp(&b.1));
+
+        let hands = hands.into_iter().enumerate()
+            .map(|(i, (line, hand))| RankedHand {
+                line,
+                rank: i + 1,
+                substituted: hand.substitution.map(|_| hand.substituted(rules)),
+                winnings: hand.bid as i64 * (i as i64 + 1),
+                cards: hand.cards,
+                hand_type: hand.hand_type,
+                bid: hand.bid,
+            })
+            .collect();
+        Report { rules: rules.name.clone(), hands }
+    }
+
+    pub fn total_winnings(&self) -> i64 {
+        self.hands.iter().map(|h| h.winnings).sum()
+    }
+
+    pub fn to_table(&self) -> String {
+        let cards_width = self.hands.iter().map(|h| h.cards.len()).max().unwrap_or(0).max(5);
+        let types : Vec<String> = self.hands.iter().map(|h| h.hand_type.to_string()).collect();
+        let type_width = types.iter().map(|t| t.len()).max().unwrap_or(0).max(4);
+
+        let mut out = String::new();
+        writeln!(out, "{:>5}  {:<cards_width$}  {:<type_width$}  {:<cards_width$}  {:>6}  {:>10}",
+            "rank", "cards", "type", "as", "bid", "winnings").unwrap();
+        for (hand, hand_type) in self.hands.iter().zip(&types) {
+            writeln!(out, "{:>5}  {:<cards_width$}  {:<type_width$}  {:<cards_width$}  {:>6}  {:>10}",
+                hand.rank, hand.cards, hand_type, hand.substituted.as_deref().unwrap_or("-"), hand.bid, hand.winnings).unwrap();
+        }
+        writeln!(out, "Total winnings under '{}': {}", self.rules, self.total_winnings()).unwrap();
+        out
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("rank,cards,type,substituted,bid,winnings\n");
+        for hand in &self.hands {
+            writeln!(out, "{},{},{},{},{},{}",
+                hand.rank, hand.cards, hand.hand_type, hand.substituted.as_deref().unwrap_or(""), hand.bid, hand.winnings).unwrap();
+        }
+        out
+    }
+
+    // Every hand ranked differently in `other`, which must be a report on the same input. Sorted by
+    // the new rank.
+    pub fn diff<'a>(&'a self, other: &'a Report) -> Vec<RankChange<'a>> {
+        let before : HashMap<usize, &RankedHand> = self.hands.iter().map(|h| (h.line, h)).collect();
+        other.hands.iter()
+            .map(|after| {
+                let before = before.get(&after.line)
+                    .unwrap_or_else(|| panic!("Hand on line {} is missing from '{}'", after.line + 1, self.rules));
+                RankChange { before, after }
+            })
+            .filter(|change| change.before.rank != change.after.rank)
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct RankChange<'a> {
+    pub before: &'a RankedHand,
+    pub after: &'a RankedHand,
+}
+impl RankChange<'_> {
+    // How many places the hand went up, negative if it went down
+    pub fn moved(&self) -> i64 {
+        self.after.rank as i64 - self.before.rank as i64
+    }
+}
+
+pub fn diff_table(before: &Report, after: &Report) -> String {
+    let changes = before.diff(after);
+    let mut out = String::new();
+    writeln!(out, "{} of {} hands move from '{}' to '{}'", changes.len(), after.hands.len(), before.rules, after.rules).unwrap();
+    for change in &changes {
+        let (b, a) = (change.before, change.after);
+        let hand_type = if b.hand_type == a.hand_type { a.hand_type.to_string() } else { format!("{} -> {}", b.hand_type, a.hand_type) };
+        writeln!(out, "  {} (bid {}): rank {} -> {} ({:+}), {hand_type}, winnings {} -> {}",
+            a.cards, a.bid, b.rank, a.rank, change.moved(), b.winnings, a.winnings).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SAMPLE;
+
+    #[test]
+    fn report_formats() {
+        let report = Report::new(SAMPLE, &RuleSet::jokers());
+        assert_eq!(report.total_winnings(), 5905);
+
+        let csv = report.to_csv();
+        assert_eq!(csv.lines().next(), Some("rank,cards,type,substituted,bid,winnings"));
+        assert_eq!(csv.lines().nth(1), Some("1,32T3K,OnePair,,765,765"));
+        assert_eq!(csv.lines().last(), Some("5,KTJJT,FourOfKind,KTTTT,220,1100"));
+
+        let table = report.to_table();
+        assert_eq!(table.lines().count(), 7);
+        assert!(table.contains("    5  KTJJT  FourOfKind  KTTTT     220        1100\n"));
+        assert!(table.ends_with("Total winnings under 'jokers': 5905\n"));
+    }
+
+    #[test]
+    fn ranking_diff() {
+        let standard = Report::new(SAMPLE, &RuleSet::standard());
+        let jokers = Report::new(SAMPLE, &RuleSet::jokers());
+
+        let moves : Vec<(&str, usize, usize)> = standard.diff(&jokers).iter()
+            .map(|c| (c.after.cards.as_str(), c.before.rank, c.after.rank))
+            .collect();
+        assert_eq!(moves, vec![("KK677", 3, 2), ("T55J5", 4, 3), ("QQQJA", 5, 4), ("KTJJT", 2, 5)]);
+        assert!(standard.diff(&standard).is_empty());
+
+        let table = diff_table(&standard, &jokers);
+        assert!(table.starts_with("4 of 5 hands move from 'standard' to 'jokers'\n"));
+        assert!(table.contains("  KTJJT (bid 220): rank 2 -> 5 (+3), TwoPair -> FourOfKind, winnings 440 -> 1100\n"));
+    }
+}
@@ -0,0 +1,287 @@
+use std::{cmp::Ordering, collections::HashMap, fmt};
+
+// How hands with the same type are split
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    // The first card that differs, in the order the cards were dealt (Camel Cards)
+    InOrder,
+    // The strongest card first, then the next strongest, and so on (poker-like)
+    Sorted,
+}
+
+// Everything that decides how hands rank: the cards and their order from weakest to strongest,
+// which of them are wild, how many cards make a hand and how ties are broken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSet {
+    pub name: String,
+    pub cards: Vec<char>,
+    pub wild: Vec<char>,
+    pub hand_size: usize,
+    pub tie_break: TieBreak,
+}
+impl RuleSet {
+    pub fn standard() -> RuleSet {
+        RuleSet {
+            name: String::from("standard"),
+            cards: "23456789TJQKA".chars().collect(),
+            wild: Vec::new(),
+            hand_size: 5,
+            tie_break: TieBreak::InOrder,
+        }
+    }
+
+    // Jokers are wild, but the weakest card when breaking ties
+    pub fn jokers() -> RuleSet {
+        RuleSet {
+            name: String::from("jokers"),
+            cards: "J23456789TQKA".chars().collect(),
+            wild: vec!['J'],
+            ..RuleSet::standard()
+        }
+    }
+
+    // `key = value` lines, with anything after a '#' ignored. Keys left out keep the standard rules.
+    //   name = jokers and twos
+    //   cards = J 2 3 4 5 6 7 8 9 T Q K A    (weakest first, spaces optional)
+    //   wild = J 2
+    //   hand_size = 7
+    //   tie_break = in-order | sorted
+    pub fn parse(config: &str) -> Result<RuleSet, String> {
+        let mut rules = RuleSet { name: String::from("custom"), ..RuleSet::standard() };
+        let cards = |value: &str| value.chars().filter(|c| !c.is_whitespace()).collect::<Vec<char>>();
+
+        for line in config.lines() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line.split_once('=')
+                .ok_or_else(|| format!("expected 'key = value', found '{line}'"))?;
+            let value = value.trim();
+            match key.trim() {
+                "name" => rules.name = value.to_string(),
+                "cards" => rules.cards = cards(value),
+                "wild" => rules.wild = cards(value),
+                "hand_size" => rules.hand_size = value.parse()
+                    .map_err(|_| format!("'{value}' is not a hand size"))?,
+                "tie_break" => rules.tie_break = match value {
+                    "in-order" => TieBreak::InOrder,
+                    "sorted" => TieBreak::Sorted,
+                    _ => return Err(format!("unknown tie break '{value}', expected in-order or sorted")),
+                },
+                key => return Err(format!("unknown key '{key}'")),
+            }
+        }
+
+        rules.validate().map(|_| rules)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.hand_size == 0 {
+            return Err(String::from("hands need at least one card"));
+        }
+        for (i, card) in self.cards.iter().enumerate() {
+            if self.cards[..i].contains(card) {
+                return Err(format!("card '{card}' is listed more than once"));
+            }
+        }
+        match self.wild.iter().find(|c| !self.cards.contains(c)) {
+            Some(card) => Err(format!("wild card '{card}' is not one of the cards")),
+            None => Ok(()),
+        }
+    }
+
+    pub fn strength(&self, card: char) -> Option<usize> {
+        self.cards.iter().position(|&c| c == card)
+    }
+
+    pub fn is_wild(&self, card: char) -> bool {
+        self.wild.contains(&card)
+    }
+}
+
+// How many of each card a hand holds, largest group first. Comparing these in order ranks hands the
+// way Camel Cards does for any hand size: five of a kind [5] beats four of a kind [4, 1], which
+// beats a full house [3, 2] and so on down to high card.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandType {
+    pub groups: Vec<usize>,
+}
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self.groups.as_slice() {
+            [5] => "FiveOfKind",
+            [4, 1] => "FourOfKind",
+            [3, 2] => "FullHouse",
+            [3, 1, 1] => "ThreeOfKind",
+            [2, 2, 1] => "TwoPair",
+            [2, 1, 1, 1] => "OnePair",
+            [1, 1, 1, 1, 1] => "HighCard",
+            groups => {
+                let groups : Vec<String> = groups.iter().map(|g| g.to_string()).collect();
+                return write!(f, "{}", groups.join("+"));
+            }
+        };
+        write!(f, "{name}")
+    }
+}
+
+// A hand as ranked under one rule set. Hands only compare by type and then their tie-break
+// strengths, so comparing hands parsed under different rule sets means nothing.
+#[derive(Debug, Clone)]
+pub struct Hand {
+    pub cards: String,
+    pub bid: i32,
+    pub hand_type: HandType,
+    // Card strengths in the order the tie break compares them
+    pub strengths: Vec<usize>,
+    // The card every wild card in the hand stands in for, if it holds any
+    pub substitution: Option<char>,
+}
+impl Hand {
+    pub fn parse(line: &str, rules: &RuleSet) -> Hand {
+        let (cards, bid) = line.split_once(' ')
+            .unwrap_or_else(|| panic!("Line was not in proper format: {line}"));
+        if cards.chars().count() != rules.hand_size {
+            panic!("Hand {cards} does not have {} cards", rules.hand_size);
+        }
+
+        let mut strengths : Vec<usize> = cards.chars()
+            .map(|card| rules.strength(card).unwrap_or_else(|| panic!("Hand {cards} has unknown card '{card}'")))
+            .collect();
+        if rules.tie_break == TieBreak::Sorted {
+            strengths.sort_by(|a, b| b.cmp(a));
+        }
+
+        let (hand_type, substitution) = best_type(cards, rules);
+        Hand {
+            cards: cards.to_string(),
+            bid: bid.trim().parse().unwrap_or_else(|_| panic!("Line was not in proper format: {line}")),
+            hand_type,
+            strengths,
+            substitution,
+        }
+    }
+
+    // The cards with every wild card replaced by the card it stands in for
+    pub fn substituted(&self, rules: &RuleSet) -> String {
+        self.cards.chars()
+            .map(|c| match self.substitution {
+                Some(card) if rules.is_wild(c) => card,
+                _ => c,
+            })
+            .collect()
+    }
+}
+
+// Wild cards are best all spent on the card there is most of, as that grows the largest group. On
+// a tie the strongest card is picked, and a hand of only wild cards becomes the strongest card
+// that isn't wild.
+fn best_type(cards: &str, rules: &RuleSet) -> (HandType, Option<char>) {
+    let mut counts : HashMap<char, usize> = HashMap::new();
+    let mut wild = 0;
+    for card in cards.chars() {
+        if rules.is_wild(card) {
+            wild += 1;
+        } else {
+            *counts.entry(card).or_insert(0) += 1;
+        }
+    }
+
+    let substitution = if wild == 0 {
+        None
+    } else {
+        counts.iter()
+            .max_by_key(|(&card, &count)| (count, rules.strength(card)))
+            .map(|(&card, _)| card)
+            .or_else(|| rules.cards.iter().rev().find(|&&c| !rules.is_wild(c)).copied())
+    };
+    if let Some(card) = substitution {
+        *counts.entry(card).or_insert(0) += wild;
+    }
+
+    let mut groups : Vec<usize> = counts.into_values().collect();
+    if groups.is_empty() {
+        // Every card is wild and none is left to stand in for
+        groups.push(wild);
+    }
+    groups.sort_by(|a, b| b.cmp(a));
+    (HandType { groups }, substitution)
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.hand_type.cmp(&other.hand_type)
+            .then_with(|| self.strengths.cmp(&other.strengths))
+    }
+}
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Hand {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SAMPLE;
+
+    fn ranked(input: &str, rules: &RuleSet) -> Vec<Hand> {
+        let mut hands : Vec<Hand> = input.lines().map(|l| Hand::parse(l, rules)).collect();
+        hands.sort();
+        hands
+    }
+
+    fn winnings(hands: &[Hand]) -> i32 {
+        hands.iter().enumerate().map(|(i, h)| h.bid * (i as i32 + 1)).sum()
+    }
+
+    #[test]
+    fn puzzle_rules() {
+        let standard = ranked(SAMPLE, &RuleSet::standard());
+        assert_eq!(winnings(&standard), 6440);
+        let order : Vec<&str> = standard.iter().map(|h| h.cards.as_str()).collect();
+        assert_eq!(order, vec!["32T3K", "KTJJT", "KK677", "T55J5", "QQQJA"]);
+
+        let jokers = ranked(SAMPLE, &RuleSet::jokers());
+        assert_eq!(winnings(&jokers), 5905);
+        assert_eq!(jokers[4].cards, "KTJJT");
+        assert_eq!(jokers[4].hand_type.to_string(), "FourOfKind");
+        assert_eq!(jokers[4].substitution, Some('T'));
+        assert_eq!(jokers[4].substituted(&RuleSet::jokers()), "KTTTT");
+        assert_eq!(Hand::parse("JJJJJ 1", &RuleSet::jokers()).substitution, Some('A'));
+    }
+
+    #[test]
+    fn custom_rules() {
+        let rules = RuleSet::parse("\
+name = jokers and twos
+cards = J 2 3 4 5 6 7 8 9 T Q K A  # weakest first
+wild = J2
+hand_size = 7
+tie_break = sorted").unwrap();
+        assert_eq!(rules.hand_size, 7);
+
+        let hands = ranked("3456789 1\nAKQT982 2\n2J33KK4 3\nAAKKQQ3 4", &rules);
+        let summary : Vec<(&str, String)> = hands.iter().map(|h| (h.cards.as_str(), h.hand_type.to_string())).collect();
+        assert_eq!(summary, vec![
+            ("3456789", String::from("1+1+1+1+1+1+1")),
+            ("AKQT982", String::from("2+1+1+1+1+1")),
+            ("AAKKQQ3", String::from("2+2+2+1")),
+            ("2J33KK4", String::from("4+2+1")),
+        ]);
+        // Sorted tie breaks ignore the order the cards were dealt in
+        assert_eq!(Hand::parse("3456789 1", &rules), Hand::parse("9876543 1", &rules));
+
+        assert!(RuleSet::parse("wild = X").is_err());
+        assert!(RuleSet::parse("cards = AA").is_err());
+        assert!(RuleSet::parse("tie_break = random").is_err());
+        assert!(RuleSet::parse("colour = red").is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use aoc_graph::NodeId;
+use num::Integer;
+
+use crate::NodeMap;
+
+// Where a ghost is depends on its node and how far it is through the directions, so it walks a
+// fixed path through those (node, direction index) states until one repeats, then loops forever.
+// Steps count from the start, so a ghost is at ..Z on every step in `prefix_hits`, and on every
+// step in `cycle_hits` plus any multiple of `length`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    pub start: NodeId,
+    // The step the loop starts on
+    pub prefix: u64,
+    pub length: u64,
+    // ..Z steps before the loop starts
+    pub prefix_hits: Vec<u64>,
+    // ..Z steps on the first time round the loop, from prefix up to prefix + length
+    pub cycle_hits: Vec<u64>,
+}
+impl GhostCycle {
+    pub fn find(node_map: &NodeMap, start: NodeId) -> GhostCycle {
+        let directions = node_map.directions.len();
+        // The step each state was first seen on, by node * directions + direction index
+        let mut seen : Vec<Option<u64>> = vec![None; node_map.nodes.node_count() * directions];
+        let mut hits = Vec::new();
+        let mut node = start;
+        let mut step = 0;
+
+        let prefix = loop {
+            let state = node as usize * directions + step as usize % directions;
+            if let Some(first) = seen[state] {
+                break first;
+            }
+            seen[state] = Some(step);
+            if node_map.nodes.label(node).ends_with('Z') {
+                hits.push(step);
+            }
+            node = node_map.next_node(node, node_map.directions[step as usize % directions]);
+            step += 1;
+        };
+
+        let cycle_hits = hits.split_off(hits.partition_point(|&hit| hit < prefix));
+        GhostCycle { start, prefix, length: step - prefix, prefix_hits: hits, cycle_hits }
+    }
+
+    pub fn hits(&self, step: u64) -> bool {
+        if step < self.prefix {
+            return self.prefix_hits.contains(&step);
+        }
+        let in_cycle = self.prefix + (step - self.prefix) % self.length;
+        self.cycle_hits.contains(&in_cycle)
+    }
+
+    // Reaches ..Z once per loop, first on the step the loop is long. Then the ghost is at ..Z on
+    // exactly the multiples of its loop, which is what the puzzle's inputs do.
+    pub fn is_periodic(&self) -> bool {
+        self.prefix_hits.is_empty() && self.cycle_hits == [self.length]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Lcm,
+    Crt,
+}
+
+// The first step every ghost is at ..Z on together, if there is one, and how it was found. When
+// every ghost is periodic that is just the LCM of their loops. Otherwise each ghost gives a choice
+// of congruences, one per ..Z in its loop, and every combination is solved with the CRT.
+pub fn earliest_common_step(ghosts: &[GhostCycle]) -> Option<(u128, Method)> {
+    if ghosts.is_empty() {
+        return None;
+    }
+    if ghosts.iter().all(|g| g.is_periodic()) {
+        let step = ghosts.iter().fold(1, |step, g| step.lcm(&(g.length as u128)));
+        return Some((step, Method::Lcm));
+    }
+
+    // Steps before some ghost's loop starts can only be that ghost's prefix hits
+    let before_loops = ghosts.iter()
+        .flat_map(|g| g.prefix_hits.iter().copied())
+        .filter(|&step| ghosts.iter().all(|g| g.hits(step)))
+        .min();
+
+    let mut congruences : Vec<(i128, i128)> = vec![(0, 1)];
+    for ghost in ghosts {
+        let length = ghost.length as i128;
+        let mut merged : Vec<(i128, i128)> = congruences.iter()
+            .flat_map(|&c| ghost.cycle_hits.iter().filter_map(move |&hit| merge(c, (hit as i128 % length, length))))
+            .collect();
+        merged.sort();
+        merged.dedup();
+        congruences = merged;
+    }
+
+    // Every ghost is in its loop from the latest prefix on
+    let start = ghosts.iter().map(|g| g.prefix).max().unwrap() as i128;
+    let in_loops = congruences.iter()
+        .map(|&(r, m)| r + ((start - r).max(0) + m - 1) / m * m)
+        .min();
+
+    let step = before_loops.map(|step| step as u128).into_iter()
+        .chain(in_loops.map(|step| step as u128))
+        .min()?;
+    Some((step, Method::Crt))
+}
+
+// The steps meeting both x = r1 (mod m1) and x = r2 (mod m2), as x = r (mod lcm(m1, m2)), or
+// None if no step does. The moduli don't have to be coprime.
+pub fn merge((r1, m1): (i128, i128), (r2, m2): (i128, i128)) -> Option<(i128, i128)> {
+    let gcd = m1.extended_gcd(&m2);
+    if (r2 - r1) % gcd.gcd != 0 {
+        return None;
+    }
+
+    // m1 * gcd.x = gcd (mod m2), so stepping r1 by m1 that many times lands on r2
+    let m2_reduced = m2 / gcd.gcd;
+    let diff = ((r2 - r1) / gcd.gcd).rem_euclid(m2_reduced);
+    let k = (diff * gcd.x.rem_euclid(m2_reduced)).rem_euclid(m2_reduced);
+    let m = m1 * m2_reduced;
+    Some(((r1 + m1 * k).rem_euclid(m), m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_node_map;
+
+    fn cycles(node_map: &NodeMap) -> Vec<GhostCycle> {
+        node_map.nodes.nodes()
+            .filter(|&id| node_map.nodes.label(id).ends_with('A'))
+            .map(|id| GhostCycle::find(node_map, id))
+            .collect()
+    }
+
+    #[test]
+    fn sample_ghosts() {
+        let node_map = parse_node_map("\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)");
+        let ghosts = cycles(&node_map);
+        let summary : Vec<(u64, u64, Vec<u64>)> = ghosts.iter().map(|g| (g.prefix, g.length, g.cycle_hits.clone())).collect();
+        assert_eq!(summary, vec![(1, 2, vec![2]), (1, 6, vec![3, 6])]);
+        assert!(ghosts[0].is_periodic());
+        assert!(!ghosts[1].is_periodic());
+        assert_eq!(earliest_common_step(&ghosts), Some((6, Method::Crt)));
+        assert_eq!(earliest_common_step(&ghosts[..1]), Some((2, Method::Lcm)));
+    }
+
+    #[test]
+    fn offset_cycles() {
+        // 1A reaches ..Z on steps 3, 7, 11... and 2A on 1, 4, 7..., which the LCM of 3 and 1 misses
+        let node_map = parse_node_map("\
+L
+
+1A = (1B, 1B)
+1B = (1C, 1C)
+1C = (1Z, 1Z)
+1Z = (1D, 1D)
+1D = (1B, 1B)
+2A = (2Z, 2Z)
+2Z = (2B, 2B)
+2B = (2C, 2C)
+2C = (2Z, 2Z)");
+        let ghosts = cycles(&node_map);
+        assert_eq!(ghosts[0], GhostCycle { start: ghosts[0].start, prefix: 1, length: 4, prefix_hits: vec![], cycle_hits: vec![3] });
+        assert_eq!(ghosts[1].cycle_hits, vec![1]);
+        assert_eq!(earliest_common_step(&ghosts), Some((7, Method::Crt)));
+
+        // A ghost that only reaches ..Z once, before settling into a loop without one
+        let mut with_stray = ghosts.clone();
+        with_stray.push(GhostCycle { start: 0, prefix: 2, length: 1, prefix_hits: vec![1], cycle_hits: vec![] });
+        assert_eq!(earliest_common_step(&with_stray), None);
+        with_stray.truncate(1);
+        with_stray.push(GhostCycle { start: 0, prefix: 4, length: 1, prefix_hits: vec![3], cycle_hits: vec![] });
+        assert_eq!(earliest_common_step(&with_stray), Some((3, Method::Crt)));
+    }
+
+    #[test]
+    fn generalized_crt() {
+        assert_eq!(merge((2, 3), (3, 5)), Some((8, 15)));
+        assert_eq!(merge((3, 4), (1, 6)), Some((7, 12)));
+        assert_eq!(merge((0, 4), (1, 6)), None);
+        assert_eq!(merge((0, 1), (5, 7)), Some((5, 7)));
+    }
+}
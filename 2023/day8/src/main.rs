@@ -1,32 +1,52 @@
-use std::collections::HashMap;
-use num::integer::lcm;
+mod ghosts;
 
+use aoc_graph::{DotStyle, Graph, NodeId};
+use aoc_runner::{json, Answer, Explained, Value};
+use ghosts::{earliest_common_step, GhostCycle};
+
+// Each node has exactly two edges, in order: left then right
 #[derive(Debug)]
 struct NodeMap<'a> {
     directions: Vec<char>,
-    nodes: HashMap<&'a str, (&'a str, &'a str)>,
+    nodes: Graph<&'a str, char>,
+}
+impl<'a> NodeMap<'a> {
+    fn next_node(&self, node: NodeId, direction: char) -> NodeId {
+        let edges = self.nodes.edges(node);
+        if direction == 'L' { edges[0].0 } else { edges[1].0 }
+    }
+
+    fn to_dot(&self) -> String {
+        self.nodes.to_dot("day8", |name| {
+            if name.ends_with('A') { DotStyle::new("doublecircle", "palegreen") }
+            else if name.ends_with('Z') { DotStyle::new("doublecircle", "salmon") }
+            else { DotStyle::default() }
+        }, |direction| Some(direction.to_string()))
+    }
 }
 
 fn parse_node_map(input: &str) -> NodeMap {
     let mut lines = input.lines();
 
-    let mut node_map = NodeMap { directions: Vec::new(), nodes: HashMap::new() };
+    let mut node_map = NodeMap { directions: Vec::new(), nodes: Graph::new_directed() };
     node_map.directions = lines.next().unwrap().trim().chars().collect();
     
     // skip empty line
     lines.next();
 
-    node_map.nodes = lines
-        .map(|x| {
-            let mut parts = x.split(" = (");
-            let key = parts.next().unwrap();
-            let mut vals = parts.next().unwrap().split(", ");
-            let left = vals.next().unwrap();
-            let right = vals.next().unwrap().trim_end_matches(")");
-
-            (key, (left, right))
-        })
-        .collect();
+    for x in lines {
+        let mut parts = x.split(" = (");
+        let key = parts.next().unwrap();
+        let mut vals = parts.next().unwrap().split(", ");
+        let left = vals.next().unwrap();
+        let right = vals.next().unwrap().trim_end_matches(")");
+
+        let key = node_map.nodes.add_node(key);
+        let left = node_map.nodes.add_node(left);
+        let right = node_map.nodes.add_node(right);
+        node_map.nodes.add_edge(key, left, 'L');
+        node_map.nodes.add_edge(key, right, 'R');
+    }
 
     node_map
 }
@@ -34,21 +54,21 @@ fn parse_node_map(input: &str) -> NodeMap {
 fn count_steps(node_map: &NodeMap, start: &str, dest: &str, dest_is_ending: bool) -> i64 {
     let mut step_count = 0;
 
-    let mut current_node = start;
+    let mut current_node = node_map.nodes.id(&start).unwrap();
     let mut next_dir_index = 0;
 
     loop {
+        let name = node_map.nodes.label(current_node);
         let hit_ending = match dest_is_ending {
-            true => current_node.ends_with(dest),
-            false => current_node == dest
+            true => name.ends_with(dest),
+            false => *name == dest
         };
         if hit_ending {
             break;
         }
 
         let direction = node_map.directions[next_dir_index];
-        let node = node_map.nodes[current_node];
-        current_node = if direction == 'L' { node.0 } else { node.1 };
+        current_node = node_map.next_node(current_node, direction);
 
         next_dir_index = (next_dir_index + 1) % node_map.directions.len();
         step_count = step_count + 1;
@@ -57,36 +77,45 @@ fn count_steps(node_map: &NodeMap, start: &str, dest: &str, dest_is_ending: bool
     step_count
 }
 
-fn get_lcm(nums: &[i64]) -> i64 {
-    assert!(!nums.is_empty());
-    if nums.len() == 1 {
-        return nums[0];
-    }
-    if nums.len() == 2 {
-        return lcm(nums[0], nums[1]);
-    }
-
-    let a = nums[0];
-    let b = get_lcm(&nums[1..]);
-    return lcm(a, b);
-}
+fn count_steps_ending(node_map: &NodeMap, start: &str) -> Option<Explained<Answer>> {
+    let ghosts : Vec<GhostCycle> = node_map.nodes.nodes()
+        .filter(|&id| node_map.nodes.label(id).ends_with(start))
+        .map(|id| GhostCycle::find(node_map, id))
+        .collect();
 
-fn count_steps_ending(node_map: &NodeMap, start: &str, dest: &str) -> i64 {
-    let node_steps : Vec<i64> = node_map.nodes
-        .keys().into_iter()
-        .filter(|k| k.ends_with(start))
-        .map(|n| count_steps(node_map, n, dest, true))
+    let ghost_cycles : Vec<Value> = ghosts.iter()
+        .map(|g| json!({
+            "start": node_map.nodes.label(g.start),
+            "prefix": g.prefix,
+            "cycle_length": g.length,
+            "prefix_hits": g.prefix_hits,
+            "cycle_hits": g.cycle_hits,
+        }))
         .collect();
 
-    get_lcm(&node_steps[0..])
+    let Some((steps, method)) = earliest_common_step(&ghosts) else {
+        println!("Part 2: the ghosts are never all on ..Z at once");
+        return None;
+    };
+    Some(Explained::new(steps.into())
+        .with("ghost_cycles", &ghost_cycles)
+        .with("method", &format!("{method:?}"))
+        .with("directions", &node_map.directions.len()))
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
     let input = include_str!("input.txt");
-    let node_map = parse_node_map(input);
-    let steps = count_steps(&node_map, "AAA", "ZZZ", false);
-    println!("Num Steps (Simple): {steps}");
+    let node_map = aoc_runner::timed("parse", || parse_node_map(input));
+    if std::env::args().any(|a| a == "--graph") {
+        print!("{}", node_map.to_dot());
+        return;
+    }
+
+    aoc_runner::answer!(1, count_steps(&node_map, "AAA", "ZZZ", false));
     
-    let steps = count_steps_ending(&node_map, "A", "Z");
-    println!("Num Steps (Ghost): {steps}");
+    if let Some(explained) = count_steps_ending(&node_map, "A") {
+        aoc_runner::explain!(2, explained);
+    }
 }
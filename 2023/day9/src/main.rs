@@ -1,3 +1,7 @@
+use std::io::BufRead;
+
+use aoc_runner::Answer;
+
 #[derive(Debug)]
 struct InputSequence {
     numbers: Vec<Vec<i64>>,
@@ -59,38 +63,39 @@ fn parse_sequence(input: &str) -> InputSequence {
     InputSequence { numbers }
 }
 
-fn part_1(input: &str) {
-    let mut sequences : Vec<InputSequence> = 
-        input.lines()
-            .map(|line| parse_sequence(line))
-            .collect();
+// Each sequence is extrapolated as soon as its line is read, so only one is held at a time
+fn part_1<R: BufRead>(reader: R) -> Answer {
+    let mut extrapolated_vals : i64 = 0;
+    aoc_runner::for_each_line(reader, |line| {
+        let mut sequence = parse_sequence(line);
+        sequence.extrapolate();
+        extrapolated_vals += sequence.numbers[0].last().unwrap();
+    });
 
-    sequences.iter_mut().for_each(|s| s.extrapolate() );
-    
-    let extrapolated_vals : i64 = sequences.iter()
-        .map(|s| s.numbers[0].last().unwrap())
-        .sum();
-
-    println!("Part 1: {extrapolated_vals}");
+    extrapolated_vals.into()
 }
 
-fn part_2(input: &str) {
-    let mut sequences : Vec<InputSequence> = 
-        input.lines()
-            .map(|line| parse_sequence(line))
-            .collect();
-
-    sequences.iter_mut().for_each(|s| s.extrapolate_backwards() );
-    
-    let extrapolated_vals : i64 = sequences.iter()
-        .map(|s| s.numbers[0].first().unwrap())
-        .sum();
+fn part_2<R: BufRead>(reader: R) -> Answer {
+    let mut extrapolated_vals : i64 = 0;
+    aoc_runner::for_each_line(reader, |line| {
+        let mut sequence = parse_sequence(line);
+        sequence.extrapolate_backwards();
+        extrapolated_vals += sequence.numbers[0].first().unwrap();
+    });
 
-    println!("Part 2: {extrapolated_vals}");
+    extrapolated_vals.into()
 }
 
+aoc_runner::install_counting_allocator!();
+
 fn main() {
+    if let Some(path) = aoc_runner::input_path() {
+        aoc_runner::answer!(1, part_1(aoc_runner::open_input(&path)));
+        aoc_runner::answer!(2, part_2(aoc_runner::open_input(&path)));
+        return;
+    }
+
     let input = include_str!("input.txt");
-    part_1(input);
-    part_2(input);
+    aoc_runner::answer!(1, part_1(input.as_bytes()));
+    aoc_runner::answer!(2, part_2(input.as_bytes()));
 }
@@ -0,0 +1,107 @@
+use aoc_runner::{now_secs, AnswerLog, AnswerRecord, Config, Outcome};
+
+use crate::days::parse_puzzle;
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (year, day) = parse_puzzle(args)?;
+    let config = Config::load();
+    let path = config.answers_path(year, day);
+    let mut log = AnswerLog::load(&path);
+
+    match args.get(2).map(|a| a.as_str()) {
+        None => {
+            print_log(&log);
+            Ok(())
+        },
+        Some("--expected") => {
+            let call = args.get(3)
+                .ok_or(String::from("expected --expected <call>, e.g. 'part_{part}(include_str!(\"input.txt\"), false)'"))?;
+            print!("{}", expected_tests(&log, call));
+            Ok(())
+        },
+        Some("mark") => {
+            if args.len() < 6 {
+                return Err(String::from("expected mark <part> <answer> <correct|too_high|too_low|incorrect>"));
+            }
+
+            let part = args[3].parse::<u32>().map_err(|_| format!("invalid part '{}'", args[3]))?;
+            let outcome = Outcome::parse(&args[5])
+                .filter(|o| o == &Outcome::Correct || o.is_wrong())
+                .ok_or(format!("invalid verdict '{}'", args[5]))?;
+
+            log.push(AnswerRecord { timestamp: now_secs(), part, answer: args[4].clone(), outcome, retry_after: 0 });
+            log.save(&path).map_err(|e| format!("failed to write {}: {e}", path.display()))
+        },
+        Some(other) => Err(format!("unknown option '{other}'")),
+    }
+}
+
+fn print_log(log: &AnswerLog) {
+    for part in 1..=2 {
+        let records : Vec<&AnswerRecord> = log.records.iter().filter(|r| r.part == part).collect();
+        if records.is_empty() {
+            continue;
+        }
+
+        println!("Part {part}:");
+        for record in records {
+            println!("  {:<20} {}", record.answer, record.outcome);
+        }
+
+        let (lower, upper) = log.bounds(part);
+        if lower.is_some() || upper.is_some() {
+            println!("  bounds: {} < answer < {}",
+                lower.map(|l| l.to_string()).unwrap_or(String::from("?")),
+                upper.map(|u| u.to_string()).unwrap_or(String::from("?")));
+        }
+    }
+}
+
+// Confirmed answers as tests in the style the day crates already use. Days call their parts in
+// all sorts of ways, so `call` is the expression producing a part's answer, with {part} standing
+// for the part number.
+pub fn expected_tests(log: &AnswerLog, call: &str) -> String {
+    let mut s = String::new();
+    for part in 1..=2 {
+        let Some(answer) = log.correct_answer(part) else { continue; };
+        let expected = if answer.parse::<i128>().is_ok() { answer.to_string() } else { format!("{answer:?}") };
+        let call = call.replace("{part}", &part.to_string());
+
+        s += &format!(concat!(
+            "    #[test]\n",
+            "    fn part{part}_input() {{\n",
+            "        let result = {call};\n",
+            "        assert_eq!(result, {expected});\n",
+            "    }}\n"), part = part, call = call, expected = expected);
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_tests_from_log() {
+        let log = AnswerLog::parse("1\t1\ttoo_low\t60\t10\n2\t1\tcorrect\t0\t24000\n3\t2\tcorrect\t0\tEFJKZ\n");
+        assert_eq!(expected_tests(&log, "part_{part}(include_str!(\"input.txt\"))"), concat!(
+            "    #[test]\n",
+            "    fn part1_input() {\n",
+            "        let result = part_1(include_str!(\"input.txt\"));\n",
+            "        assert_eq!(result, 24000);\n",
+            "    }\n",
+            "    #[test]\n",
+            "    fn part2_input() {\n",
+            "        let result = part_2(include_str!(\"input.txt\"));\n",
+            "        assert_eq!(result, \"EFJKZ\");\n",
+            "    }\n"));
+    }
+
+    // tests/expected_tests.rs compiles and runs this output against a stand-in day
+    #[test]
+    fn expected_tests_compile() {
+        let log = AnswerLog::parse("1\t1\tcorrect\t0\t2\n2\t2\tcorrect\t0\tEFJKZ\n");
+        let call = "part_{part}(\"JXFP\\nEFJKZ\\n\")";
+        assert_eq!(expected_tests(&log, call), include_str!("../tests/generated/fixture.rs"));
+    }
+}
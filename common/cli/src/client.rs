@@ -0,0 +1,229 @@
+use std::time::Duration;
+
+use aoc_runner::{Config, Outcome};
+
+const USER_AGENT : &str = "github.com/essbuh/AdventOfCode aoc-cli";
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        AocClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        match &config.session {
+            Some(session) => Ok(AocClient::new(&config.base_url, session)),
+            None => Err(String::from("no session token: set AOC_SESSION or `session = ...` in the config file")),
+        }
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn fetch_input(&self, year: u32, day: u32) -> Result<String, String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let response = self.agent.get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|e| describe_error(&url, e))?;
+
+        response.into_string().map_err(|e| format!("failed to read {url}: {e}"))
+    }
+
+    pub fn submit(&self, year: u32, day: u32, part: u32, answer: &str) -> Result<(Outcome, u64), String> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let response = self.agent.post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[ ("level", &part.to_string()), ("answer", answer) ])
+            .map_err(|e| describe_error(&url, e))?;
+
+        let body = response.into_string().map_err(|e| format!("failed to read {url}: {e}"))?;
+        parse_submit_response(&body)
+    }
+}
+
+fn describe_error(url: &str, error: ureq::Error) -> String {
+    match error {
+        ureq::Error::Status(400, _) => format!("{url}: 400 Bad Request (is the session token valid?)"),
+        ureq::Error::Status(404, _) => format!("{url}: 404 Not Found (is the puzzle unlocked yet?)"),
+        ureq::Error::Status(code, _) => format!("{url}: HTTP {code}"),
+        ureq::Error::Transport(t) => format!("{url}: {t}"),
+    }
+}
+
+// Plain text of the first <article>, which is where the site puts its verdict
+fn article_text(html: &str) -> String {
+    let start = html.find("<article").unwrap_or(0);
+    let end = html[start..].find("</article>").map(|e| start + e).unwrap_or(html.len());
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html[start..end].chars() {
+        match c {
+            '<' => { in_tag = true; },
+            '>' => { in_tag = false; },
+            _ if !in_tag => { text.push(c); },
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+// "You have 4m 32s left to wait" / "You have 32s left to wait"
+fn parse_wait_left(text: &str) -> Option<u64> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+
+    let mut total = 0;
+    for part in text[start..end].split_whitespace() {
+        let (value, unit) = part.split_at(part.len() - 1);
+        let value = value.parse::<u64>().ok()?;
+        total += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(total)
+}
+
+// "Please wait one minute before trying again" / "please wait 5 minutes before trying again"
+fn parse_retry_after(text: &str) -> u64 {
+    let lower = text.to_lowercase();
+    let Some(start) = lower.find("wait ") else { return 0; };
+    let mut words = lower[start + 5..].split_whitespace();
+
+    let count = match words.next() {
+        Some("one") | Some("a") => 1,
+        Some(n) => n.parse::<u64>().unwrap_or(0),
+        None => 0,
+    };
+    match words.next() {
+        Some(unit) if unit.starts_with("minute") => count * 60,
+        Some(unit) if unit.starts_with("second") => count,
+        _ => 0,
+    }
+}
+
+pub fn parse_submit_response(html: &str) -> Result<(Outcome, u64), String> {
+    let text = article_text(html);
+
+    if text.contains("That's the right answer") {
+        Ok((Outcome::Correct, 0))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Ok((Outcome::AlreadySolved, 0))
+    } else if text.contains("You gave an answer too recently") {
+        Ok((Outcome::RateLimited, parse_wait_left(&text).unwrap_or(60)))
+    } else if text.contains("That's not the right answer") {
+        let outcome = if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        };
+        Ok((outcome, parse_retry_after(&text)))
+    } else {
+        Err(format!("unrecognised response: {text}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::{BufRead, BufReader, Read, Write}, net::TcpListener, thread};
+
+    // Answers a single request with `body`, handing back the request line and body it received
+    fn mock_server(body: &'static str) -> (String, thread::JoinHandle<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+
+            let mut content_length = 0;
+            let mut cookie = String::new();
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                let lower = header.to_lowercase();
+                if let Some(value) = lower.strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                if lower.starts_with("cookie:") {
+                    cookie = header.trim().to_string();
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+
+            let response = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len());
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+
+            (request_line.trim().to_string() + " " + &cookie, String::from_utf8(request_body).unwrap())
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn fetch_input_from_mock() {
+        let (url, server) = mock_server("1 2 3\n4 5 6\n");
+        let client = AocClient::new(&url, "secret");
+        let input = client.fetch_input(2023, 9).unwrap();
+        let (request, _) = server.join().unwrap();
+
+        assert_eq!(input, "1 2 3\n4 5 6\n");
+        assert_eq!(request, "GET /2023/day/9/input HTTP/1.1 Cookie: session=secret");
+    }
+
+    #[test]
+    fn submit_to_mock() {
+        let (url, server) = mock_server("<main><article><p>That's not the right answer; your answer is too high. \
+            Please wait one minute before trying again.</p></article></main>");
+        let client = AocClient::new(&url, "secret");
+        let result = client.submit(2023, 9, 2, "1234").unwrap();
+        let (request, body) = server.join().unwrap();
+
+        assert_eq!(result, (Outcome::TooHigh, 60));
+        assert!(request.starts_with("POST /2023/day/9/answer HTTP/1.1"));
+        assert_eq!(body, "level=2&answer=1234");
+    }
+
+    #[test]
+    fn parse_responses() {
+        assert_eq!(parse_submit_response("<article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article>"),
+            Ok((Outcome::Correct, 0)));
+        assert_eq!(parse_submit_response("<article><p>That's not the right answer; your answer is too low. Please wait 5 minutes before trying again.</p></article>"),
+            Ok((Outcome::TooLow, 300)));
+        assert_eq!(parse_submit_response("<article><p>That's not the right answer. If you're stuck...</p></article>"),
+            Ok((Outcome::Incorrect, 0)));
+        assert_eq!(parse_submit_response("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 4m 32s left to wait.</p></article>"),
+            Ok((Outcome::RateLimited, 272)));
+        assert_eq!(parse_submit_response("<article><p>You don't seem to be solving the right level. Did you already complete it?</p></article>"),
+            Ok((Outcome::AlreadySolved, 0)));
+        assert!(parse_submit_response("<html>maintenance</html>").is_err());
+    }
+}
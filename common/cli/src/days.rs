@@ -0,0 +1,46 @@
+use std::{path::PathBuf, process::Command};
+
+pub fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+pub fn package_name(year: u32, day: u32) -> String {
+    format!("aoc{year}_day{day}")
+}
+
+pub fn crate_dir(year: u32, day: u32) -> PathBuf {
+    workspace_root().join(year.to_string()).join(format!("day{day}"))
+}
+
+// Year and day of a puzzle, whether or not we have a crate for it yet
+pub fn parse_puzzle(args: &[String]) -> Result<(u32, u32), String> {
+    if args.len() < 2 {
+        return Err(String::from("expected <year> <day>"));
+    }
+
+    let year = args[0].parse::<u32>().map_err(|_| format!("invalid year '{}'", args[0]))?;
+    let day = args[1].parse::<u32>().map_err(|_| format!("invalid day '{}'", args[1]))?;
+    if !(1..=25).contains(&day) {
+        return Err(format!("invalid day '{day}'"));
+    }
+
+    Ok((year, day))
+}
+
+pub fn parse_year_day(args: &[String]) -> Result<(u32, u32), String> {
+    let (year, day) = parse_puzzle(args)?;
+    if !crate_dir(year, day).join("Cargo.toml").exists() {
+        return Err(format!("no crate for {year} day {day}"));
+    }
+
+    Ok((year, day))
+}
+
+// `cargo run` for a day's binary, with extra arguments forwarded to it
+pub fn cargo_run(year: u32, day: u32, args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO"));
+    command.current_dir(workspace_root())
+        .args(["run", "-q", "-p", &package_name(year, day), "--"])
+        .args(args);
+    command
+}
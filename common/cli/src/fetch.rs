@@ -0,0 +1,26 @@
+use std::fs;
+
+use aoc_runner::Config;
+
+use crate::{client::AocClient, days::parse_puzzle};
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (year, day) = parse_puzzle(args)?;
+    let force = args[2..].iter().any(|a| a == "--force");
+
+    let config = Config::load();
+    let path = config.input_path(year, day);
+    if path.exists() && !force {
+        println!("{} already exists (use --force to download again)", path.display());
+        return Ok(());
+    }
+
+    let client = AocClient::from_config(&config)?;
+    let input = client.fetch_input(year, day)?;
+
+    fs::create_dir_all(config.day_dir(year)).map_err(|e| format!("failed to create {}: {e}", config.day_dir(year).display()))?;
+    fs::write(&path, input).map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    println!("Wrote {}", path.display());
+
+    Ok(())
+}
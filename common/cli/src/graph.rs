@@ -0,0 +1,24 @@
+use crate::days::{cargo_run, parse_year_day};
+
+// Days whose binary understands --graph
+const GRAPH_DAYS : &[(u32, u32)] = &[ (2023, 3), (2023, 8), (2023, 20), (2023, 23), (2023, 25) ];
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (year, day) = parse_year_day(args)?;
+    if !GRAPH_DAYS.contains(&(year, day)) {
+        return Err(format!("{year} day {day} has no graph export"));
+    }
+
+    let mut day_args = vec![ "--graph" ];
+    day_args.extend(args[2..].iter().map(|a| a.as_str()));
+
+    let status = cargo_run(year, day, &day_args)
+        .status()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+
+    if !status.success() {
+        return Err(format!("{year} day {day} exited with {status}"));
+    }
+
+    Ok(())
+}
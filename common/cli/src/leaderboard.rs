@@ -0,0 +1,313 @@
+use std::{collections::BTreeMap, fs};
+
+use serde::{Deserialize, Serialize};
+
+// Puzzles unlock at midnight US Eastern, which is 05:00 UTC in December
+const UNLOCK_UTC_OFFSET_SECS : i64 = 5 * 3600;
+
+#[derive(Debug, Deserialize)]
+struct Star {
+    get_star_ts: i64,
+    #[serde(default)]
+    star_index: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Member {
+    id: u64,
+    name: Option<String>,
+    #[serde(default)]
+    stars: u32,
+    #[serde(default)]
+    local_score: i64,
+    // day -> part -> star
+    #[serde(default)]
+    completion_day_level: BTreeMap<String, BTreeMap<String, Star>>,
+}
+impl Member {
+    fn display_name(&self) -> String {
+        self.name.clone().unwrap_or(format!("(anonymous user #{})", self.id))
+    }
+
+    fn star(&self, day: u32, part: u32) -> Option<&Star> {
+        self.completion_day_level.get(&day.to_string())?.get(&part.to_string())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Leaderboard {
+    event: String,
+    members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct MemberDay {
+    pub day: u32,
+    pub part1_ts: Option<i64>,
+    pub part2_ts: Option<i64>,
+    // Seconds since the puzzle unlocked
+    pub part1_elapsed: Option<i64>,
+    pub part2_elapsed: Option<i64>,
+    pub part1_to_part2: Option<i64>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct MemberReport {
+    pub id: u64,
+    pub name: String,
+    pub stars: u32,
+    pub local_score: i64,
+    pub computed_local_score: i64,
+    pub days: Vec<MemberDay>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct RankEntry {
+    pub rank: usize,
+    pub name: String,
+    pub ts: i64,
+    pub elapsed: i64,
+    pub points: i64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct DayRanking {
+    pub day: u32,
+    pub part: u32,
+    pub ranking: Vec<RankEntry>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct LeaderboardReport {
+    pub event: String,
+    pub members: Vec<MemberReport>,
+    pub days: Vec<DayRanking>,
+}
+
+fn unlock_time(year: i64, day: u32) -> i64 {
+    days_from_civil(year, 12, day as i64) * 86400 + UNLOCK_UTC_OFFSET_SECS
+}
+
+// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm)
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+pub fn format_duration(secs: i64) -> String {
+    if secs >= 86400 {
+        format!("{}d {:02}:{:02}:{:02}", secs / 86400, (secs % 86400) / 3600, (secs % 3600) / 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+    }
+}
+
+pub fn analyze(json: &str) -> Result<LeaderboardReport, String> {
+    let board : Leaderboard = serde_json::from_str(json).map_err(|e| format!("invalid leaderboard json: {e}"))?;
+    let year = board.event.parse::<i64>().map_err(|_| format!("invalid event '{}'", board.event))?;
+    let members : Vec<&Member> = board.members.values().collect();
+
+    let max_day = members.iter()
+        .flat_map(|m| m.completion_day_level.keys())
+        .filter_map(|d| d.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0);
+
+    // Local score: for every star, the first member to get it scores N points, the next N-1, ...
+    let mut computed_scores = vec![0; members.len()];
+    let mut days = Vec::new();
+    for day in 1..=max_day {
+        for part in 1..=2 {
+            let mut finishers : Vec<(usize, &Star)> = members.iter().enumerate()
+                .filter_map(|(i, m)| m.star(day, part).map(|s| (i, s)))
+                .collect();
+            if finishers.is_empty() {
+                continue;
+            }
+            finishers.sort_by_key(|(_, s)| (s.get_star_ts, s.star_index));
+
+            let ranking = finishers.iter().enumerate()
+                .map(|(rank, (member, star))| {
+                    let points = (members.len() - rank) as i64;
+                    computed_scores[*member] += points;
+                    RankEntry {
+                        rank: rank + 1,
+                        name: members[*member].display_name(),
+                        ts: star.get_star_ts,
+                        elapsed: star.get_star_ts - unlock_time(year, day),
+                        points,
+                    }
+                })
+                .collect();
+
+            days.push(DayRanking { day, part, ranking });
+        }
+    }
+
+    let mut member_reports : Vec<MemberReport> = members.iter().enumerate()
+        .map(|(i, member)| {
+            let days = (1..=max_day)
+                .filter_map(|day| {
+                    let part1 = member.star(day, 1).map(|s| s.get_star_ts);
+                    let part2 = member.star(day, 2).map(|s| s.get_star_ts);
+                    part1?;
+
+                    let unlock = unlock_time(year, day);
+                    Some(MemberDay {
+                        day,
+                        part1_ts: part1,
+                        part2_ts: part2,
+                        part1_elapsed: part1.map(|t| t - unlock),
+                        part2_elapsed: part2.map(|t| t - unlock),
+                        part1_to_part2: part1.zip(part2).map(|(a, b)| b - a),
+                    })
+                })
+                .collect();
+
+            MemberReport {
+                id: member.id,
+                name: member.display_name(),
+                stars: member.stars,
+                local_score: member.local_score,
+                computed_local_score: computed_scores[i],
+                days,
+            }
+        })
+        .collect();
+
+    member_reports.sort_by(|a, b| b.computed_local_score.cmp(&a.computed_local_score).then(a.name.cmp(&b.name)));
+
+    Ok(LeaderboardReport { event: board.event, members: member_reports, days })
+}
+
+fn print_report(report: &LeaderboardReport) {
+    let time = |t: Option<i64>| t.map(format_duration).unwrap_or(String::from("-"));
+
+    println!("Advent of Code {} - private leaderboard\n", report.event);
+    println!("{:<4} {:<30} {:>5} {:>6} {:>8}", "", "Member", "Stars", "Score", "Computed");
+    for (i, member) in report.members.iter().enumerate() {
+        let mismatch = if member.local_score != member.computed_local_score { " *" } else { "" };
+        println!("{:<4} {:<30} {:>5} {:>6} {:>8}{mismatch}", format!("{})", i + 1), member.name, member.stars, member.local_score, member.computed_local_score);
+    }
+
+    for member in &report.members {
+        if member.days.is_empty() {
+            continue;
+        }
+
+        println!("\n{}", member.name);
+        println!("  {:>3} {:>14} {:>14} {:>14}", "Day", "Part 1", "Part 2", "Delta");
+        for day in &member.days {
+            println!("  {:>3} {:>14} {:>14} {:>14}", day.day, time(day.part1_elapsed), time(day.part2_elapsed), time(day.part1_to_part2));
+        }
+    }
+
+    for day in &report.days {
+        println!("\nDay {} part {}", day.day, day.part);
+        for entry in &day.ranking {
+            println!("  {:>3}) {:<30} {:>14} {:>4} pts", entry.rank, entry.name, format_duration(entry.elapsed), entry.points);
+        }
+    }
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut file = None;
+    let mut as_json = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--file" => { file = Some(args.next().ok_or("--file needs a path")?.clone()); },
+            "--json" => { as_json = true; },
+            other => return Err(format!("unknown option '{other}'")),
+        }
+    }
+
+    let file = file.ok_or("expected --file <board.json>")?;
+    let json = fs::read_to_string(&file).map_err(|e| format!("failed to read {file}: {e}"))?;
+    let report = analyze(&json)?;
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?);
+    } else {
+        print_report(&report);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2023-12-01 05:00:00 UTC
+    const DAY_1 : i64 = 1701406800;
+
+    fn sample() -> String {
+        format!(r#"{{
+            "owner_id": 1, "event": "2023",
+            "members": {{
+                "1": {{ "id": 1, "name": "alice", "stars": 3, "local_score": 11, "global_score": 0, "last_star_ts": 0,
+                    "completion_day_level": {{
+                        "1": {{ "1": {{ "get_star_ts": {a1}, "star_index": 10 }}, "2": {{ "get_star_ts": {a2}, "star_index": 11 }} }},
+                        "2": {{ "1": {{ "get_star_ts": {a3}, "star_index": 20 }} }}
+                    }} }},
+                "2": {{ "id": 2, "name": null, "stars": 2, "local_score": 4, "global_score": 0, "last_star_ts": 0,
+                    "completion_day_level": {{
+                        "1": {{ "1": {{ "get_star_ts": {b1}, "star_index": 5 }}, "2": {{ "get_star_ts": {b2}, "star_index": 30 }} }}
+                    }} }},
+                "3": {{ "id": 3, "name": "carol", "stars": 0, "local_score": 0, "global_score": 0, "last_star_ts": 0,
+                    "completion_day_level": {{}} }}
+            }}
+        }}"#,
+            a1 = DAY_1 + 600, a2 = DAY_1 + 900, a3 = DAY_1 + 86400 + 60,
+            b1 = DAY_1 + 300, b2 = DAY_1 + 4000)
+    }
+
+    #[test]
+    fn unlock_times() {
+        assert_eq!(unlock_time(2023, 1), DAY_1);
+        assert_eq!(unlock_time(2020, 25), 1608872400);
+        assert_eq!(format_duration(3725), "01:02:05");
+        assert_eq!(format_duration(90061), "1d 01:01:01");
+    }
+
+    #[test]
+    fn analyze_sample() {
+        let report = analyze(&sample()).unwrap();
+        assert_eq!(report.event, "2023");
+
+        let names : Vec<&str> = report.members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["alice", "(anonymous user #2)", "carol"]);
+
+        // Day 1 part 1: #2 first (3), alice second (2); part 2: alice (3), #2 (2); day 2 part 1: alice (3)
+        let scores : Vec<i64> = report.members.iter().map(|m| m.computed_local_score).collect();
+        assert_eq!(scores, vec![8, 5, 0]);
+
+        let alice = &report.members[0];
+        assert_eq!(alice.days[0], MemberDay {
+            day: 1,
+            part1_ts: Some(DAY_1 + 600),
+            part2_ts: Some(DAY_1 + 900),
+            part1_elapsed: Some(600),
+            part2_elapsed: Some(900),
+            part1_to_part2: Some(300),
+        });
+        assert_eq!(alice.days[1].part1_elapsed, Some(60));
+        assert_eq!(alice.days[1].part1_to_part2, None);
+
+        assert_eq!(report.days.len(), 3);
+        assert_eq!(report.days[0].ranking[0].name, "(anonymous user #2)");
+        assert_eq!(report.days[0].ranking[0].points, 3);
+        assert_eq!(report.days[2].day, 2);
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(analyze("{ not json").is_err());
+    }
+}
@@ -0,0 +1,63 @@
+mod answers;
+mod client;
+mod days;
+mod fetch;
+mod graph;
+mod leaderboard;
+mod report;
+mod scale;
+mod snapshots;
+mod submit;
+mod watch;
+
+use std::{env, process::ExitCode};
+
+const USAGE : &str = "\
+Usage: aoc <command> [args]
+
+Commands:
+  graph <year> <day> [day args]           Print the day's puzzle input as Graphviz DOT
+  fetch <year> <day> [--force]            Download the day's input into the inputs directory
+  submit <year> <day> <part> <answer>     Submit an answer and record the result
+  answers <year> <day>                    List every answer the day has produced or submitted
+  answers <year> <day> --expected <call>  Print the confirmed answers as unit tests calling <call>
+  answers <year> <day> mark <part> <answer> <correct|too_high|too_low|incorrect>
+                                          Record a verdict given outside of `aoc submit`
+  watch <year> <day>                      Rebuild and re-run tests and the day on every change
+  report --year <year> [-o report.html]   Run every day and write an HTML summary of the year
+  snapshots <year> <day> [review|accept|reject [names]|update]
+                                          Review, accept or reject the day's changed test renders
+  scale <year> <day> [--sizes 10,100,1000] [--runs <n>] [--svg <file>]
+                                          Time the day on generated inputs and fit its complexity
+  leaderboard --file <board.json> [--json]
+                                          Analyze a downloaded private leaderboard offline
+
+Settings are read from $AOC_CONFIG (default ~/.config/aoc/config) as `key = value`
+lines (session, base_url, inputs_dir) and can be overridden with AOC_SESSION,
+AOC_BASE_URL and AOC_INPUTS_DIR.";
+
+fn main() -> ExitCode {
+    let args : Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(|a| a.as_str()) {
+        Some("graph") => graph::run(&args[1..]),
+        Some("fetch") => fetch::run(&args[1..]),
+        Some("scale") => scale::run(&args[1..]),
+        Some("snapshots") => snapshots::run(&args[1..]),
+        Some("submit") => submit::run(&args[1..]),
+        Some("answers") => answers::run(&args[1..]),
+        Some("watch") => watch::run(&args[1..]),
+        Some("report") => report::run(&args[1..]),
+        Some("leaderboard") => leaderboard::run(&args[1..]),
+        Some("help") | Some("--help") | Some("-h") => { println!("{USAGE}"); Ok(()) },
+        _ => Err(String::from(USAGE)),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
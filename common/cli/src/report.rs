@@ -0,0 +1,279 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use aoc_runner::{AnswerLog, Config};
+
+use crate::days::{package_name, workspace_root};
+use crate::watch::parse_answer_line;
+
+// Days whose binary prints a text render of its solution with --render
+const RENDER_DAYS : &[(u32, u32)] = &[ (2023, 16), (2023, 17), (2023, 23) ];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Unknown,
+}
+
+#[derive(Debug, Default)]
+pub struct DayReport {
+    pub day: u32,
+    pub answers: BTreeMap<u32, String>,
+    pub expected: BTreeMap<u32, String>,
+    pub parse_time: Duration,
+    pub part_times: BTreeMap<u32, Duration>,
+    pub render: Option<String>,
+    pub error: Option<String>,
+}
+impl DayReport {
+    pub fn status(&self, part: u32) -> Status {
+        match (self.answers.get(&part), self.expected.get(&part)) {
+            (Some(answer), Some(expected)) if answer == expected => Status::Pass,
+            (_, Some(_)) => Status::Fail,
+            _ => Status::Unknown,
+        }
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.part_times.values().sum::<Duration>()
+    }
+}
+
+// The answers confirmed in the day's answer log (by `aoc submit` or `aoc answers ... mark`)
+fn expected_answers(log: &AnswerLog) -> BTreeMap<u32, String> {
+    (1..=2)
+        .filter_map(|part| Some((part, log.correct_answer(part)?.to_string())))
+        .collect()
+}
+
+fn day_number(dir: &Path) -> Option<u32> {
+    dir.file_name()?.to_str()?.strip_prefix("day")?.parse().ok()
+}
+
+pub(crate) fn target_dir() -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR").map(PathBuf::from).unwrap_or(workspace_root().join("target"))
+}
+
+// Times each part by when its answer line arrives, minus any stages reported in between
+pub(crate) fn run_day(binary: &Path, args: &[&str], report: &mut DayReport) -> Result<(), String> {
+    let start = Instant::now();
+    let mut child = Command::new(binary)
+        .args(args)
+        .env("AOC_TIMINGS", "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("failed to run {}: {e}", binary.display()))?;
+
+    let mut last = start;
+    let mut stages = Duration::ZERO;
+    for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        if let Some((part, answer)) = parse_answer_line(&line) {
+            report.part_times.insert(part, last.elapsed().saturating_sub(stages));
+            report.answers.insert(part, answer);
+            last = Instant::now();
+            stages = Duration::ZERO;
+        } else if let Some(nanos) = line.strip_prefix("Stage ").and_then(|l| l.split_once(": ")).and_then(|(_, t)| t.strip_suffix("ns")) {
+            let time = Duration::from_nanos(nanos.parse().unwrap_or(0));
+            report.parse_time += time;
+            stages += time;
+        }
+    }
+
+    let status = child.wait().map_err(|e| e.to_string())?;
+    if !status.success() {
+        return Err(format!("exited with {status}"));
+    }
+    Ok(())
+}
+
+fn render_day(binary: &Path) -> Option<String> {
+    let output = Command::new(binary).arg("--render").stderr(Stdio::null()).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+pub(crate) fn format_time(time: Duration) -> String {
+    if time.as_secs_f64() >= 1.0 {
+        format!("{:.2}s", time.as_secs_f64())
+    } else {
+        format!("{:.2}ms", time.as_secs_f64() * 1000.0)
+    }
+}
+
+const STYLE : &str = "\
+body { background: #0f0f23; color: #cccccc; font-family: 'Source Code Pro', monospace; margin: 2em; }
+h1, h2 { color: #00cc00; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3em 0.8em; text-align: left; border-bottom: 1px solid #333340; }
+.pass { color: #00cc00; }
+.fail { color: #ff4040; }
+.unknown { color: #999999; }
+.bar { display: flex; width: 30em; height: 0.9em; background: #1a1a33; }
+.bar span { display: block; height: 100%; }
+.parse { background: #9999cc; }
+.part1 { background: #ffff66; }
+.part2 { background: #66ccff; }
+pre { background: #10101a; padding: 1em; overflow-x: auto; line-height: 1; }
+";
+
+pub fn render_html(year: u32, days: &[DayReport]) -> String {
+    let slowest = days.iter().map(|d| d.total_time()).max().unwrap_or_default().as_secs_f64().max(1e-9);
+    let percent = |t: Duration| t.as_secs_f64() / slowest * 100.0;
+
+    let checked : Vec<Status> = days.iter().flat_map(|d| [d.status(1), d.status(2)]).filter(|s| *s != Status::Unknown).collect();
+    let passed = checked.iter().filter(|s| **s == Status::Pass).count();
+
+    let mut html = format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code {year}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n");
+    html += &format!("<h1>Advent of Code {year}</h1>\n<p>{passed} of {} checked answers match the expected values.</p>\n", checked.len());
+    html += "<table>\n<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Time <span class=\"parse\">&nbsp;parse&nbsp;</span> <span class=\"part1\">&nbsp;part 1&nbsp;</span> <span class=\"part2\">&nbsp;part 2&nbsp;</span></th><th></th></tr>\n";
+
+    for day in days {
+        html += &format!("<tr><td>{}</td>", day.day);
+        for part in 1..=2 {
+            let (class, mark) = match day.status(part) {
+                Status::Pass => ("pass", " &#10003;"),
+                Status::Fail => ("fail", " &#10007;"),
+                Status::Unknown => ("unknown", ""),
+            };
+            let answer = day.answers.get(&part).map(|a| escape(a)).unwrap_or(String::from("-"));
+            let title = match (day.status(part), day.expected.get(&part)) {
+                (Status::Fail, Some(expected)) => format!(" title=\"expected {}\"", escape(expected)),
+                _ => String::new(),
+            };
+            html += &format!("<td class=\"{class}\"{title}>{answer}{mark}</td>");
+        }
+
+        match &day.error {
+            Some(error) => { html += &format!("<td class=\"fail\" colspan=\"2\">{}</td>", escape(error)); },
+            None => {
+                html += "<td><div class=\"bar\">";
+                html += &format!("<span class=\"parse\" style=\"width: {:.2}%\"></span>", percent(day.parse_time));
+                for part in 1..=2 {
+                    let time = day.part_times.get(&part).copied().unwrap_or_default();
+                    html += &format!("<span class=\"part{part}\" style=\"width: {:.2}%\" title=\"part {part}: {}\"></span>", percent(time), format_time(time));
+                }
+                html += &format!("</div></td><td>{}</td>", format_time(day.total_time()));
+            }
+        }
+        html += "</tr>\n";
+    }
+    html += "</table>\n";
+
+    for day in days {
+        if let Some(render) = &day.render {
+            html += &format!("<h2>Day {}</h2>\n<pre>{}</pre>\n", day.day, escape(render));
+        }
+    }
+
+    html += "</body>\n</html>\n";
+    html
+}
+
+pub(crate) fn build_release(packages: &[String]) -> Result<(), String> {
+    let mut command = Command::new(env!("CARGO"));
+    command.current_dir(workspace_root()).args(["build", "-q", "--release", "--bins"]);
+    for package in packages {
+        command.args(["-p", package]);
+    }
+
+    let status = command.status().map_err(|e| format!("failed to run cargo: {e}"))?;
+    if !status.success() {
+        return Err(String::from("build failed"));
+    }
+    Ok(())
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut year = None;
+    let mut output = PathBuf::from("report.html");
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => { year = Some(args.next().and_then(|y| y.parse::<u32>().ok()).ok_or("--year needs a year")?); },
+            "-o" | "--output" => { output = PathBuf::from(args.next().ok_or("-o needs a path")?); },
+            other => return Err(format!("unknown option '{other}'")),
+        }
+    }
+    let year = year.ok_or("expected --year <year>")?;
+
+    let year_dir = workspace_root().join(year.to_string());
+    let mut day_numbers : Vec<u32> = fs::read_dir(&year_dir)
+        .map_err(|e| format!("failed to read {}: {e}", year_dir.display()))?
+        .flatten()
+        .filter(|e| e.path().join("Cargo.toml").exists())
+        .filter_map(|e| day_number(&e.path()))
+        .collect();
+    day_numbers.sort();
+
+    let packages : Vec<String> = day_numbers.iter().map(|day| package_name(year, *day)).collect();
+    build_release(&packages)?;
+
+    let config = Config::load();
+    let mut days = Vec::new();
+    for day in day_numbers {
+        eprintln!("Running {year} day {day}...");
+        let mut report = DayReport { day, expected: expected_answers(&AnswerLog::load(&config.answers_path(year, day))), ..Default::default() };
+
+        let binary = target_dir().join("release").join(package_name(year, day));
+        if !binary.exists() {
+            report.error = Some(String::from("no binary"));
+        } else if let Err(e) = run_day(&binary, &[], &mut report) {
+            report.error = Some(e);
+        } else if RENDER_DAYS.contains(&(year, day)) {
+            report.render = render_day(&binary);
+        }
+
+        days.push(report);
+    }
+
+    fs::write(&output, render_html(year, &days)).map_err(|e| format!("failed to write {}: {e}", output.display()))?;
+    println!("Wrote {}", output.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_from_log() {
+        let log = AnswerLog::parse("1\t1\ttoo_low\t60\t3000\n2\t1\tcorrect\t0\t3841\n3\t2\tincorrect\t0\tabd\n");
+        let expected = expected_answers(&log);
+        assert_eq!(expected.get(&1).map(|s| s.as_str()), Some("3841"));
+        assert_eq!(expected.get(&2), None);
+    }
+
+    #[test]
+    fn html_report() {
+        let mut day = DayReport { day: 3, ..Default::default() };
+        day.answers.insert(1, String::from("10"));
+        day.answers.insert(2, String::from("<20>"));
+        day.expected.insert(1, String::from("10"));
+        day.expected.insert(2, String::from("21"));
+        day.part_times.insert(1, Duration::from_millis(5));
+        day.render = Some(String::from("#.<\n"));
+
+        assert_eq!(day.status(1), Status::Pass);
+        assert_eq!(day.status(2), Status::Fail);
+
+        let html = render_html(2023, &[day]);
+        assert!(html.contains("1 of 2 checked answers"));
+        assert!(html.contains("<td class=\"pass\">10 &#10003;</td>"));
+        assert!(html.contains("<td class=\"fail\" title=\"expected 21\">&lt;20&gt; &#10007;</td>"));
+        assert!(html.contains("<pre>#.&lt;\n</pre>"));
+        assert!(html.contains("width: 100.00%"));
+    }
+}
@@ -0,0 +1,288 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use aoc_runner::format_bytes;
+
+use crate::{
+    days::{package_name, parse_year_day},
+    report::{build_release, format_time, run_day, target_dir, DayReport},
+};
+
+// Days whose binary prints a generated input for --generate <size>
+const GENERATOR_DAYS : &[(u32, u32)] = &[ (2023, 11), (2023, 22) ];
+
+const DEFAULT_SIZES : [usize; 3] = [10, 100, 1000];
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Measurement {
+    pub size: usize,
+    pub input_bytes: usize,
+    pub parse: Duration,
+    pub parts: BTreeMap<u32, Duration>,
+}
+impl Measurement {
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts.values().sum::<Duration>()
+    }
+
+    // Every timed series, in the order they are shown
+    fn series(&self) -> Vec<(String, Duration)> {
+        let mut series = vec![(String::from("parse"), self.parse)];
+        series.extend(self.parts.iter().map(|(part, time)| (format!("part {part}"), *time)));
+        series.push((String::from("total"), self.total()));
+        series
+    }
+}
+
+// Least-squares slope of log(time) against log(size): time grows like size^k. Zero times (stages
+// a day doesn't have) are left out, and at least two sizes are needed.
+pub fn fit_exponent(points: &[(usize, Duration)]) -> Option<f64> {
+    let logs : Vec<(f64, f64)> = points.iter()
+        .filter(|(size, time)| *size > 0 && !time.is_zero())
+        .map(|(size, time)| ((*size as f64).ln(), time.as_secs_f64().ln()))
+        .collect();
+    if logs.len() < 2 {
+        return None;
+    }
+
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance : f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance : f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+fn series_points(measurements: &[Measurement]) -> Vec<(String, Vec<(usize, Duration)>)> {
+    let mut series : Vec<(String, Vec<(usize, Duration)>)> = Vec::new();
+    for measurement in measurements {
+        for (name, time) in measurement.series() {
+            match series.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, points)) => points.push((measurement.size, time)),
+                None => series.push((name, vec![(measurement.size, time)])),
+            }
+        }
+    }
+    series
+}
+
+pub fn format_table(measurements: &[Measurement]) -> String {
+    let series = series_points(measurements);
+
+    let mut rows = vec![ ["size", "input"].iter().map(|s| s.to_string())
+        .chain(series.iter().map(|(name, _)| name.clone()))
+        .chain(["k".to_string()])
+        .collect::<Vec<String>>() ];
+    for (i, measurement) in measurements.iter().enumerate() {
+        // Exponent between this size and the previous one, to spot where the growth changes
+        let local = match i {
+            0 => None,
+            _ => fit_exponent(&[(measurements[i - 1].size, measurements[i - 1].total()), (measurement.size, measurement.total())]),
+        };
+        rows.push([measurement.size.to_string(), format_bytes(measurement.input_bytes)].into_iter()
+            .chain(measurement.series().iter().map(|(_, time)| format_time(*time)))
+            .chain([local.map(|k| format!("{k:.2}")).unwrap_or(String::from("-"))])
+            .collect());
+    }
+
+    let widths : Vec<usize> = (0..rows[0].len())
+        .map(|i| rows.iter().map(|row| row.get(i).map_or(0, |c| c.len())).max().unwrap())
+        .collect();
+    let mut text = String::new();
+    for row in &rows {
+        let cells : Vec<String> = row.iter().zip(&widths).map(|(cell, width)| format!("{cell:>width$}")).collect();
+        text += &cells.join("  ");
+        text += "\n";
+    }
+
+    text += "\nFitted exponent (time ~ size^k):\n";
+    for (name, points) in &series {
+        match fit_exponent(points) {
+            Some(k) => text += &format!("  {name:<8} k = {k:.2}\n"),
+            None => text += &format!("  {name:<8} k = -\n"),
+        }
+    }
+    text
+}
+
+const COLORS : [&str; 5] = ["#9999cc", "#ffff66", "#66ccff", "#ff9966", "#00cc00"];
+
+// Log-log chart of every series, one decade per grid line
+pub fn render_svg(title: &str, measurements: &[Measurement]) -> String {
+    let (width, height, margin) = (640.0, 400.0, 70.0);
+    let series = series_points(measurements);
+
+    let logs : Vec<(f64, f64)> = series.iter()
+        .flat_map(|(_, points)| points.iter())
+        .filter(|(size, time)| *size > 0 && !time.is_zero())
+        .map(|(size, time)| ((*size as f64).log10(), time.as_secs_f64().log10()))
+        .collect();
+    let bounds = |values: Vec<f64>| {
+        let min = values.iter().cloned().fold(f64::INFINITY, f64::min).floor();
+        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max).ceil();
+        if min.is_finite() { (min, max.max(min + 1.0)) } else { (0.0, 1.0) }
+    };
+    let (min_x, max_x) = bounds(logs.iter().map(|(x, _)| *x).collect());
+    let (min_y, max_y) = bounds(logs.iter().map(|(_, y)| *y).collect());
+    let to_x = |x: f64| margin + (x - min_x) / (max_x - min_x) * (width - 2.0 * margin);
+    let to_y = |y: f64| height - margin - (y - min_y) / (max_y - min_y) * (height - 2.0 * margin);
+
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" font-family=\"monospace\" font-size=\"12\">\n");
+    svg += &format!("<rect width=\"{width}\" height=\"{height}\" fill=\"#0f0f23\"/>\n");
+    svg += &format!("<text x=\"{margin}\" y=\"30\" fill=\"#00cc00\" font-size=\"16\">{title}</text>\n");
+
+    for decade in (min_x as i32)..=(max_x as i32) {
+        let x = to_x(decade as f64);
+        svg += &format!("<line x1=\"{x:.1}\" y1=\"{margin}\" x2=\"{x:.1}\" y2=\"{:.1}\" stroke=\"#333340\"/>\n", height - margin);
+        svg += &format!("<text x=\"{x:.1}\" y=\"{:.1}\" fill=\"#cccccc\" text-anchor=\"middle\">{}</text>\n", height - margin + 18.0, 10f64.powi(decade));
+    }
+    for decade in (min_y as i32)..=(max_y as i32) {
+        let y = to_y(decade as f64);
+        svg += &format!("<line x1=\"{margin}\" y1=\"{y:.1}\" x2=\"{:.1}\" y2=\"{y:.1}\" stroke=\"#333340\"/>\n", width - margin);
+        svg += &format!("<text x=\"{:.1}\" y=\"{:.1}\" fill=\"#cccccc\" text-anchor=\"end\">{}</text>\n", margin - 6.0, y + 4.0, format_time(Duration::from_secs_f64(10f64.powi(decade))));
+    }
+    svg += &format!("<text x=\"{:.1}\" y=\"{:.1}\" fill=\"#cccccc\" text-anchor=\"middle\">size</text>\n", width / 2.0, height - 20.0);
+
+    for (i, (name, points)) in series.iter().enumerate() {
+        let color = if name == "total" { COLORS[COLORS.len() - 1] } else { COLORS[i % (COLORS.len() - 1)] };
+        let coords : Vec<String> = points.iter()
+            .filter(|(size, time)| *size > 0 && !time.is_zero())
+            .map(|(size, time)| format!("{:.1},{:.1}", to_x((*size as f64).log10()), to_y(time.as_secs_f64().log10())))
+            .collect();
+        if coords.is_empty() {
+            continue;
+        }
+
+        svg += &format!("<polyline points=\"{}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"2\"/>\n", coords.join(" "));
+        for coord in &coords {
+            let (x, y) = coord.split_once(',').unwrap();
+            svg += &format!("<circle cx=\"{x}\" cy=\"{y}\" r=\"3\" fill=\"{color}\"/>\n");
+        }
+
+        let label = match fit_exponent(points) {
+            Some(k) => format!("{name} (k = {k:.2})"),
+            None => name.clone(),
+        };
+        svg += &format!("<text x=\"{:.1}\" y=\"{:.1}\" fill=\"{color}\">{label}</text>\n", margin + 10.0, margin + 16.0 * i as f64);
+    }
+
+    svg += "</svg>\n";
+    svg
+}
+
+fn measure(binary: &PathBuf, input: &PathBuf, size: usize) -> Result<Measurement, String> {
+    let output = Command::new(binary)
+        .args(["--generate", &size.to_string()])
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("failed to run {}: {e}", binary.display()))?;
+    if !output.status.success() {
+        return Err(format!("generating size {size} failed with {}", output.status));
+    }
+    fs::write(input, &output.stdout).map_err(|e| format!("failed to write {}: {e}", input.display()))?;
+
+    let mut report = DayReport::default();
+    let result = run_day(binary, &["--input", input.to_str().unwrap()], &mut report);
+    fs::remove_file(input).ok();
+    result.map_err(|e| format!("size {size}: {e}"))?;
+
+    Ok(Measurement { size, input_bytes: output.stdout.len(), parse: report.parse_time, parts: report.part_times })
+}
+
+fn parse_sizes(text: &str) -> Result<Vec<usize>, String> {
+    let sizes : Vec<usize> = text.split(',')
+        .map(|s| s.trim().parse::<usize>().map_err(|_| format!("invalid size '{s}'")))
+        .collect::<Result<_, _>>()?;
+    if sizes.is_empty() || sizes.contains(&0) {
+        return Err(String::from("sizes must be positive"));
+    }
+    Ok(sizes)
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (year, day) = parse_year_day(args)?;
+
+    let mut sizes = DEFAULT_SIZES.to_vec();
+    let mut runs = 1;
+    let mut svg_path = None;
+    let mut options = args[2..].iter();
+    while let Some(arg) = options.next() {
+        match arg.as_str() {
+            "--sizes" => { sizes = parse_sizes(options.next().ok_or("--sizes needs a list like 10,100,1000")?)?; },
+            "--runs" => { runs = options.next().and_then(|r| r.parse::<usize>().ok()).filter(|r| *r > 0).ok_or("--runs needs a count")?; },
+            "--svg" => { svg_path = Some(PathBuf::from(options.next().ok_or("--svg needs a path")?)); },
+            other => return Err(format!("unknown option '{other}'")),
+        }
+    }
+
+    if !GENERATOR_DAYS.contains(&(year, day)) {
+        return Err(format!("{year} day {day} has no input generator (--generate <size>)"));
+    }
+
+    let package = package_name(year, day);
+    build_release(std::slice::from_ref(&package))?;
+    let binary = target_dir().join("release").join(&package);
+
+    let mut measurements = Vec::new();
+    for size in sizes {
+        eprintln!("Running {year} day {day} at size {size}...");
+        let input = std::env::temp_dir().join(format!("aoc_scale_{year}_{day}_{size}_{}.txt", std::process::id()));
+
+        // The fastest of several runs is the least disturbed by everything else on the machine
+        let mut best : Option<Measurement> = None;
+        for _ in 0..runs {
+            let measurement = measure(&binary, &input, size)?;
+            if best.as_ref().is_none_or(|b| measurement.total() < b.total()) {
+                best = Some(measurement);
+            }
+        }
+        measurements.push(best.unwrap());
+    }
+
+    print!("{}", format_table(&measurements));
+    if let Some(path) = svg_path {
+        let svg = render_svg(&format!("{year} day {day}"), &measurements);
+        fs::write(&path, svg).map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(size: usize, parse_us: u64, part_us: u64) -> Measurement {
+        Measurement {
+            size,
+            input_bytes: size * 10,
+            parse: Duration::from_micros(parse_us),
+            parts: BTreeMap::from([(1, Duration::from_micros(part_us))]),
+        }
+    }
+
+    #[test]
+    fn exponents() {
+        let quadratic = [(10, Duration::from_micros(100)), (100, Duration::from_micros(10_000)), (1000, Duration::from_micros(1_000_000))];
+        assert!((fit_exponent(&quadratic).unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(fit_exponent(&quadratic[..1]), None);
+        assert_eq!(fit_exponent(&[(10, Duration::ZERO), (100, Duration::ZERO)]), None);
+
+        let measurements = [measurement(10, 10, 1_000), measurement(100, 100, 1_000_000)];
+        let table = format_table(&measurements);
+        assert!(table.starts_with("size   input   parse  part 1   total     k\n"), "{table}");
+        assert!(table.contains("  parse    k = 1.00\n  part 1   k = 3.00\n"), "{table}");
+        assert_eq!(parse_sizes("10, 100,1000"), Ok(vec![10, 100, 1000]));
+        assert!(parse_sizes("10,x").is_err());
+
+        let svg = render_svg("2023 day 11", &measurements);
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<polyline").count(), 3);
+        assert!(svg.contains("part 1 (k = 3.00)"));
+    }
+}
@@ -0,0 +1,120 @@
+use std::{fs, path::Path, process::Command};
+
+use aoc_runner::{diff_lines, pending_path, snapshot_path, PENDING_EXTENSION};
+
+use crate::days::{crate_dir, package_name, parse_year_day, workspace_root};
+
+// Names of the renders left waiting for review by failed snapshot tests
+pub fn pending_snapshots(dir: &Path) -> Vec<String> {
+    let suffix = format!(".{PENDING_EXTENSION}");
+    let mut names : Vec<String> = fs::read_dir(dir).into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| e.file_name().to_str()?.strip_suffix(&suffix).map(String::from))
+        .collect();
+    names.sort();
+    names
+}
+
+pub fn accept(dir: &Path, name: &str) -> Result<(), String> {
+    fs::rename(pending_path(dir, name), snapshot_path(dir, name))
+        .map_err(|e| format!("failed to accept '{name}': {e}"))
+}
+
+pub fn reject(dir: &Path, name: &str) -> Result<(), String> {
+    fs::remove_file(pending_path(dir, name))
+        .map_err(|e| format!("failed to reject '{name}': {e}"))
+}
+
+fn review(dir: &Path, names: &[String]) {
+    for name in names {
+        let expected = fs::read_to_string(snapshot_path(dir, name)).ok();
+        let actual = fs::read_to_string(pending_path(dir, name)).unwrap_or_default();
+        match &expected {
+            Some(_) => println!("{name}: changed"),
+            None => println!("{name}: new"),
+        }
+        print!("{}", diff_lines(expected.as_deref().unwrap_or(""), &actual));
+        println!();
+    }
+}
+
+// Which pending snapshots a command applies to: the ones named, or all of them
+fn selected(pending: &[String], names: &[String]) -> Result<Vec<String>, String> {
+    if names.is_empty() {
+        return Ok(pending.to_vec());
+    }
+    match names.iter().find(|n| !pending.contains(n)) {
+        Some(missing) => Err(format!("no pending snapshot '{missing}'")),
+        None => Ok(names.to_vec()),
+    }
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (year, day) = parse_year_day(args)?;
+    let dir = crate_dir(year, day).join("snapshots");
+    let pending = pending_snapshots(&dir);
+
+    match args.get(2).map(|a| a.as_str()) {
+        None | Some("review") => {
+            if pending.is_empty() {
+                println!("No pending snapshots for {year} day {day}");
+            }
+            review(&dir, &pending);
+        },
+        Some("accept") => {
+            for name in selected(&pending, &args[3..])? {
+                accept(&dir, &name)?;
+                println!("Accepted {name}");
+            }
+        },
+        Some("reject") => {
+            for name in selected(&pending, &args[3..])? {
+                reject(&dir, &name)?;
+                println!("Rejected {name}");
+            }
+        },
+        // Re-runs the day's tests, writing every render straight into its snapshot
+        Some("update") => {
+            let status = Command::new(env!("CARGO"))
+                .current_dir(workspace_root())
+                .args(["test", "-q", "-p", &package_name(year, day)])
+                .env("AOC_UPDATE_SNAPSHOTS", "1")
+                .status()
+                .map_err(|e| format!("failed to run cargo: {e}"))?;
+            if !status.success() {
+                return Err(format!("tests failed with {status}"));
+            }
+        },
+        Some(other) => return Err(format!("unknown snapshots command '{other}'")),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_and_reject() {
+        let dir = std::env::temp_dir().join(format!("aoc_snapshots_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(snapshot_path(&dir, "a"), "old\n").unwrap();
+        fs::write(pending_path(&dir, "a"), "new\n").unwrap();
+        fs::write(pending_path(&dir, "b"), "b\n").unwrap();
+
+        let pending = pending_snapshots(&dir);
+        assert_eq!(pending, vec!["a", "b"]);
+        assert_eq!(selected(&pending, &[String::from("b")]), Ok(vec![String::from("b")]));
+        assert!(selected(&pending, &[String::from("c")]).is_err());
+
+        accept(&dir, "a").unwrap();
+        reject(&dir, "b").unwrap();
+        let accepted = fs::read_to_string(snapshot_path(&dir, "a")).unwrap();
+        let remaining = pending_snapshots(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(accepted, "new\n");
+        assert!(remaining.is_empty());
+    }
+}
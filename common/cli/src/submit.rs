@@ -0,0 +1,57 @@
+use aoc_runner::{now_secs, AnswerLog, AnswerRecord, Config, Outcome};
+
+use crate::{client::AocClient, days::parse_puzzle};
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (year, day) = parse_puzzle(args)?;
+    if args.len() < 4 {
+        return Err(String::from("expected <year> <day> <part> <answer>"));
+    }
+
+    let part = match args[2].as_str() {
+        "1" => 1,
+        "2" => 2,
+        other => return Err(format!("invalid part '{other}'")),
+    };
+    let answer = args[3].trim();
+    if answer.is_empty() {
+        return Err(String::from("refusing to submit an empty answer"));
+    }
+
+    let config = Config::load();
+    let log_path = config.answers_path(year, day);
+    let mut log = AnswerLog::load(&log_path);
+
+    if let Some(correct) = log.correct_answer(part) {
+        return Err(format!("part {part} was already solved with {correct}"));
+    }
+    if let Some(previous) = log.find_guess(part, answer) {
+        return Err(format!("{answer} was already submitted for part {part} and was {}", previous.outcome));
+    }
+
+    let now = now_secs();
+    let next_allowed = log.next_allowed_submit();
+    if now < next_allowed {
+        return Err(format!("rate limited locally, try again in {}s", next_allowed - now));
+    }
+
+    let client = AocClient::from_config(&config)?;
+    let (outcome, retry_after) = client.submit(year, day, part, answer)?;
+
+    log.push(AnswerRecord { timestamp: now, part, answer: answer.to_string(), outcome, retry_after });
+    log.save(&log_path).map_err(|e| format!("failed to write {}: {e}", log_path.display()))?;
+
+    match outcome {
+        Outcome::Correct => println!("{answer} is correct!"),
+        Outcome::RateLimited => println!("Answered too recently, wait {retry_after}s"),
+        Outcome::AlreadySolved => println!("Part {part} is already solved"),
+        _ => {
+            println!("{answer} is {outcome}");
+            if retry_after > 0 {
+                println!("Wait {retry_after}s before trying again");
+            }
+        }
+    }
+
+    Ok(())
+}
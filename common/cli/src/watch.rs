@@ -0,0 +1,288 @@
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, BufReader},
+    path::Path,
+    process::{Command, Stdio},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::days::{cargo_run, crate_dir, package_name, parse_year_day, workspace_root};
+
+// Editors tend to write a file several times in a row; wait for things to settle before rebuilding
+const DEBOUNCE : Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartResult {
+    pub answer: String,
+    // Time since the previous part finished (or since the run started)
+    pub time: Duration,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TestSummary {
+    pub passed: u32,
+    pub failed: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct RunSummary {
+    pub tests: Option<TestSummary>,
+    pub test_time: Duration,
+    pub parts: BTreeMap<u32, PartResult>,
+    pub run_time: Duration,
+    pub succeeded: bool,
+}
+
+// "Part 2: 1234" as printed by aoc_runner::answer!
+pub fn parse_answer_line(line: &str) -> Option<(u32, String)> {
+    let (part, answer) = line.strip_prefix("Part ")?.split_once(": ")?;
+    Some((part.parse().ok()?, answer.trim().to_string()))
+}
+
+// Sums every "test result: ok. 3 passed; 1 failed; ..." line cargo test prints
+pub fn parse_test_results(output: &str) -> Option<TestSummary> {
+    let mut summary = None;
+    for line in output.lines() {
+        let Some(rest) = line.trim().strip_prefix("test result: ") else { continue; };
+        let total = summary.get_or_insert(TestSummary::default());
+
+        for field in rest.split(';') {
+            let words : Vec<&str> = field.split_whitespace().collect();
+            let Some(position) = words.iter().position(|w| *w == "passed" || *w == "failed") else { continue; };
+            let Some(count) = position.checked_sub(1).and_then(|i| words[i].parse::<u32>().ok()) else { continue; };
+            if words[position] == "passed" {
+                total.passed += count;
+            } else {
+                total.failed += count;
+            }
+        }
+    }
+    summary
+}
+
+fn format_time(time: Duration) -> String {
+    if time.as_secs_f64() >= 1.0 {
+        format!("{:.2}s", time.as_secs_f64())
+    } else {
+        format!("{:.1}ms", time.as_secs_f64() * 1000.0)
+    }
+}
+
+fn format_time_change(current: Duration, previous: Option<Duration>) -> String {
+    match previous {
+        Some(previous) if !previous.is_zero() => {
+            let change = (current.as_secs_f64() / previous.as_secs_f64() - 1.0) * 100.0;
+            format!("{} (was {}, {change:+.0}%)", format_time(current), format_time(previous))
+        },
+        _ => format_time(current),
+    }
+}
+
+// One line per stage and part, comparing against the previous run when there is one
+pub fn diff_runs(current: &RunSummary, previous: Option<&RunSummary>) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    if let Some(tests) = &current.tests {
+        let was = match previous.and_then(|p| p.tests.as_ref()) {
+            Some(old) if old != tests => format!(" (was {} passed, {} failed)", old.passed, old.failed),
+            _ => String::new(),
+        };
+        lines.push(format!("tests:  {} passed, {} failed{was} in {}",
+            tests.passed, tests.failed, format_time_change(current.test_time, previous.map(|p| p.test_time))));
+    }
+
+    let mut parts : Vec<u32> = current.parts.keys().copied().collect();
+    if let Some(previous) = previous {
+        parts.extend(previous.parts.keys().filter(|p| !current.parts.contains_key(p)));
+        parts.sort();
+    }
+
+    for part in parts {
+        let old = previous.and_then(|p| p.parts.get(&part));
+        let line = match (current.parts.get(&part), old) {
+            (Some(new), Some(old)) if new.answer == old.answer =>
+                format!("part {part}: {} (unchanged) in {}", new.answer, format_time_change(new.time, Some(old.time))),
+            (Some(new), Some(old)) =>
+                format!("part {part}: {} (was {}) in {}", new.answer, old.answer, format_time_change(new.time, Some(old.time))),
+            (Some(new), None) =>
+                format!("part {part}: {} (new) in {}", new.answer, format_time(new.time)),
+            (None, Some(old)) =>
+                format!("part {part}: no answer (was {})", old.answer),
+            (None, None) => continue,
+        };
+        lines.push(line);
+    }
+
+    if current.succeeded {
+        lines.push(format!("total:  {}", format_time_change(current.run_time, previous.map(|p| p.run_time))));
+    } else {
+        lines.push(String::from("run failed"));
+    }
+
+    lines
+}
+
+fn build(year: u32, day: u32) -> Result<(), String> {
+    let status = Command::new(env!("CARGO"))
+        .current_dir(workspace_root())
+        .args(["build", "-q", "--all-targets", "-p", &package_name(year, day)])
+        .status()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+
+    if !status.success() {
+        return Err(String::from("build failed"));
+    }
+    Ok(())
+}
+
+fn run_tests(year: u32, day: u32, summary: &mut RunSummary) -> Result<(), String> {
+    let start = Instant::now();
+    let output = Command::new(env!("CARGO"))
+        .current_dir(workspace_root())
+        .args(["test", "-q", "-p", &package_name(year, day)])
+        .output()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+    summary.test_time = start.elapsed();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    summary.tests = parse_test_results(&stdout);
+
+    if !output.status.success() {
+        print!("{stdout}");
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+    Ok(())
+}
+
+// Streams the binary's output so each part can be timed as its answer comes out
+fn run_day(year: u32, day: u32, summary: &mut RunSummary) -> Result<(), String> {
+    let start = Instant::now();
+    let mut child = cargo_run(year, day, &[])
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+
+    let mut last = Instant::now();
+    for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        match parse_answer_line(&line) {
+            Some((part, answer)) => {
+                summary.parts.insert(part, PartResult { answer, time: last.elapsed() });
+                last = Instant::now();
+            },
+            None => println!("{line}"),
+        }
+    }
+
+    let status = child.wait().map_err(|e| e.to_string())?;
+    summary.run_time = start.elapsed();
+    summary.succeeded = status.success();
+    Ok(())
+}
+
+fn run_once(year: u32, day: u32) -> Result<RunSummary, String> {
+    let mut summary = RunSummary::default();
+    build(year, day)?;
+    run_tests(year, day, &mut summary)?;
+    run_day(year, day, &mut summary)?;
+    Ok(summary)
+}
+
+fn is_relevant(path: &Path) -> bool {
+    let in_target = path.components().any(|c| c.as_os_str() == "target");
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    !in_target && matches!(extension, "rs" | "txt" | "toml")
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (year, day) = parse_year_day(args)?;
+    let dir = crate_dir(year, day);
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_))
+                && event.paths.iter().any(|p| is_relevant(p)) {
+                let _ = sender.send(());
+            }
+        }
+    }).map_err(|e| format!("failed to start watcher: {e}"))?;
+
+    watcher.watch(&dir, RecursiveMode::Recursive)
+        .map_err(|e| format!("failed to watch {}: {e}", dir.display()))?;
+
+    println!("Watching {} (ctrl-c to stop)", dir.display());
+
+    let mut previous : Option<RunSummary> = None;
+    loop {
+        println!("\n=== {year} day {day} ===");
+        match run_once(year, day) {
+            Ok(summary) => {
+                for line in diff_runs(&summary, previous.as_ref()) {
+                    println!("{line}");
+                }
+                previous = Some(summary);
+            },
+            Err(e) => eprintln!("{e}"),
+        }
+
+        // Block until something changes, then drain the burst of events that follows
+        receiver.recv().map_err(|_| String::from("watcher stopped"))?;
+        while receiver.recv_timeout(DEBOUNCE).is_ok() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(parts: &[(u32, &str, u64)], run_ms: u64) -> RunSummary {
+        RunSummary {
+            tests: Some(TestSummary { passed: 2, failed: 0 }),
+            test_time: Duration::from_millis(500),
+            parts: parts.iter()
+                .map(|(part, answer, ms)| (*part, PartResult { answer: answer.to_string(), time: Duration::from_millis(*ms) }))
+                .collect(),
+            run_time: Duration::from_millis(run_ms),
+            succeeded: true,
+        }
+    }
+
+    #[test]
+    fn parse_output() {
+        assert_eq!(parse_answer_line("Part 1: 6440"), Some((1, String::from("6440"))));
+        assert_eq!(parse_answer_line("Partial: 3"), None);
+
+        let output = "\nrunning 2 tests\n..\ntest result: ok. 2 passed; 0 failed; 0 ignored\n\n\
+            running 1 test\nF\ntest result: FAILED. 0 passed; 1 failed; 0 ignored\n";
+        assert_eq!(parse_test_results(output), Some(TestSummary { passed: 2, failed: 1 }));
+        assert_eq!(parse_test_results("nothing here"), None);
+    }
+
+    #[test]
+    fn diff_against_previous() {
+        let first = summary(&[(1, "10", 100)], 200);
+        assert_eq!(diff_runs(&first, None), vec![
+            "tests:  2 passed, 0 failed in 500.0ms",
+            "part 1: 10 (new) in 100.0ms",
+            "total:  200.0ms",
+        ]);
+
+        let second = summary(&[(1, "10", 50), (2, "7", 20)], 100);
+        assert_eq!(diff_runs(&second, Some(&first)), vec![
+            "tests:  2 passed, 0 failed in 500.0ms (was 500.0ms, +0%)",
+            "part 1: 10 (unchanged) in 50.0ms (was 100.0ms, -50%)",
+            "part 2: 7 (new) in 20.0ms",
+            "total:  100.0ms (was 200.0ms, -50%)",
+        ]);
+
+        let third = summary(&[(2, "8", 20)], 100);
+        assert_eq!(diff_runs(&third, Some(&second))[1..3], [
+            String::from("part 1: no answer (was 10)"),
+            String::from("part 2: 8 (was 7) in 20.0ms (was 20.0ms, +0%)"),
+        ]);
+    }
+}
@@ -0,0 +1,16 @@
+// A stand-in for a day crate, with parts returning an `aoc_runner::Answer` like the days do.
+// generated/fixture.rs is what `aoc answers --expected` prints for it (checked by the
+// expected_tests_compile unit test), so a change that stops the output compiling fails here.
+fn part_1(input: &str) -> aoc_runner::Answer {
+    input.lines().count().into()
+}
+
+fn part_2(input: &str) -> aoc_runner::Answer {
+    input.lines().last().unwrap_or_default().into()
+}
+
+mod generated {
+    use super::*;
+
+    include!("generated/fixture.rs");
+}
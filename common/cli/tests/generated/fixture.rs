@@ -0,0 +1,10 @@
+    #[test]
+    fn part1_input() {
+        let result = part_1("JXFP\nEFJKZ\n");
+        assert_eq!(result, 2);
+    }
+    #[test]
+    fn part2_input() {
+        let result = part_2("JXFP\nEFJKZ\n");
+        assert_eq!(result, "EFJKZ");
+    }
@@ -0,0 +1,195 @@
+use std::{collections::VecDeque, hash::Hash};
+
+use crate::{graph::Graph, interner::NodeId};
+
+impl<L: Hash + Eq + Clone, W: Clone> Graph<L, W> {
+    // Nodes in the order they're reached from start
+    pub fn bfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited = vec![false; self.node_count()];
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        visited[start as usize] = true;
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for next in self.neighbors(node) {
+                if !visited[next as usize] {
+                    visited[next as usize] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        order
+    }
+
+    // Edge-count distance from start to every node, None if unreachable
+    pub fn bfs_distances(&self, start: NodeId) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.node_count()];
+        let mut queue = VecDeque::from([start]);
+        distances[start as usize] = Some(0);
+
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[node as usize].unwrap();
+            for next in self.neighbors(node) {
+                if distances[next as usize].is_none() {
+                    distances[next as usize] = Some(distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    // Pre-order depth first walk from start, visiting edges in insertion order
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited = vec![false; self.node_count()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            if visited[node as usize] {
+                continue;
+            }
+
+            visited[node as usize] = true;
+            order.push(node);
+
+            // Reversed so the first edge is popped first
+            stack.extend(self.neighbors(node).collect::<Vec<NodeId>>().into_iter().rev()
+                .filter(|n| !visited[*n as usize]));
+        }
+
+        order
+    }
+
+    // Directed graphs are treated as undirected here (weakly connected components)
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut undirected: Vec<Vec<NodeId>> = (0..self.node_count())
+            .map(|i| self.neighbors(i as NodeId).collect())
+            .collect();
+        if self.is_directed() {
+            for (from, to, _) in self.all_edges() {
+                undirected[to as usize].push(from);
+            }
+        }
+
+        let mut component_of = vec![usize::MAX; self.node_count()];
+        let mut components = Vec::new();
+
+        for node in self.nodes() {
+            if component_of[node as usize] != usize::MAX {
+                continue;
+            }
+
+            let index = components.len();
+            let mut component = Vec::new();
+            let mut stack = vec![node];
+            component_of[node as usize] = index;
+
+            while let Some(n) = stack.pop() {
+                component.push(n);
+                for &next in &undirected[n as usize] {
+                    if component_of[next as usize] == usize::MAX {
+                        component_of[next as usize] = index;
+                        stack.push(next);
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components
+    }
+
+    // Kahn's algorithm, returns None if the graph has a cycle
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        let mut in_degree = vec![0; self.node_count()];
+        for (_, to, _) in self.all_edges() {
+            in_degree[to as usize] += 1;
+        }
+
+        let mut queue: VecDeque<NodeId> = self.nodes()
+            .filter(|n| in_degree[*n as usize] == 0)
+            .collect();
+        let mut order = Vec::new();
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for next in self.neighbors(node) {
+                in_degree[next as usize] -= 1;
+                if in_degree[next as usize] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if order.len() == self.node_count() { Some(order) } else { None }
+    }
+
+    // Tarjan's algorithm, iterative so deep graphs don't blow the stack.
+    // Components come out in reverse topological order of the condensed graph.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        const UNVISITED: usize = usize::MAX;
+
+        let count = self.node_count();
+        let mut index = vec![UNVISITED; count];
+        let mut low_link = vec![0; count];
+        let mut on_stack = vec![false; count];
+        let mut stack = Vec::new();
+        let mut next_index = 0;
+        let mut components = Vec::new();
+
+        for root in self.nodes() {
+            if index[root as usize] != UNVISITED {
+                continue;
+            }
+
+            // (node, next edge to look at)
+            let mut call_stack = vec![(root, 0)];
+            while let Some(&mut (node, ref mut edge)) = call_stack.last_mut() {
+                let n = node as usize;
+                if *edge == 0 && index[n] == UNVISITED {
+                    index[n] = next_index;
+                    low_link[n] = next_index;
+                    next_index += 1;
+                    stack.push(node);
+                    on_stack[n] = true;
+                }
+
+                if let Some(&(next, _)) = self.edges(node).get(*edge) {
+                    *edge += 1;
+                    let m = next as usize;
+                    if index[m] == UNVISITED {
+                        call_stack.push((next, 0));
+                    } else if on_stack[m] {
+                        low_link[n] = low_link[n].min(index[m]);
+                    }
+                    continue;
+                }
+
+                call_stack.pop();
+                if let Some(&(parent, _)) = call_stack.last() {
+                    let p = parent as usize;
+                    low_link[p] = low_link[p].min(low_link[n]);
+                }
+
+                if low_link[n] == index[n] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member as usize] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+}
@@ -0,0 +1,66 @@
+use std::{fmt::Display, hash::Hash};
+
+use crate::graph::Graph;
+
+#[derive(Debug, Default, Clone)]
+pub struct DotStyle {
+    pub shape: Option<&'static str>,
+    pub color: Option<&'static str>,
+    pub label: Option<String>,
+}
+impl DotStyle {
+    pub fn new(shape: &'static str, color: &'static str) -> Self {
+        DotStyle { shape: Some(shape), color: Some(color), label: None }
+    }
+
+    fn attributes(&self) -> Vec<String> {
+        let mut attributes = Vec::new();
+        if let Some(shape) = self.shape {
+            attributes.push(format!("shape={shape}"));
+        }
+        if let Some(color) = self.color {
+            attributes.push(format!("style=filled, fillcolor={}", quote(color)));
+        }
+        if let Some(label) = &self.label {
+            attributes.push(format!("label={}", quote(label)));
+        }
+        attributes
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl<L: Hash + Eq + Clone + Display, W: Clone> Graph<L, W> {
+    // Graphviz source for the graph. Node ids are the labels, styles and edge labels come from the callbacks.
+    pub fn to_dot<N, E>(&self, name: &str, node_style: N, edge_label: E) -> String
+    where
+        N: Fn(&L) -> DotStyle,
+        E: Fn(&W) -> Option<String>,
+    {
+        let (keyword, arrow) = if self.is_directed() { ("digraph", "->") } else { ("graph", "--") };
+
+        let mut s = format!("{keyword} {} {{\n", quote(name));
+        for node in self.nodes() {
+            let label = self.label(node);
+            let attributes = node_style(label).attributes();
+            if attributes.is_empty() {
+                s += &format!("    {};\n", quote(&label.to_string()));
+            } else {
+                s += &format!("    {} [{}];\n", quote(&label.to_string()), attributes.join(", "));
+            }
+        }
+
+        for (from, to, weight) in self.all_edges() {
+            s += &format!("    {} {arrow} {}", quote(&self.label(from).to_string()), quote(&self.label(to).to_string()));
+            match edge_label(weight) {
+                Some(label) => { s += &format!(" [label={}];\n", quote(&label)); },
+                None => { s += ";\n"; }
+            }
+        }
+
+        s += "}\n";
+        s
+    }
+}
@@ -0,0 +1,99 @@
+use std::hash::Hash;
+
+use crate::interner::{Interner, NodeId};
+
+#[derive(Debug, Clone)]
+pub struct Graph<L, W = ()> {
+    nodes: Interner<L>,
+    adjacency: Vec<Vec<(NodeId, W)>>,
+    directed: bool,
+}
+impl<L: Hash + Eq + Clone, W: Clone> Graph<L, W> {
+    pub fn new_directed() -> Self {
+        Graph { nodes: Interner::new(), adjacency: Vec::new(), directed: true }
+    }
+
+    pub fn new_undirected() -> Self {
+        Graph { nodes: Interner::new(), adjacency: Vec::new(), directed: false }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn add_node(&mut self, label: L) -> NodeId {
+        let id = self.nodes.intern(label);
+        if self.adjacency.len() <= id as usize {
+            self.adjacency.push(Vec::new());
+        }
+        id
+    }
+
+    // Edges keep their insertion order, so callers can rely on e.g. edges(id)[0] being "left"
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: W) {
+        if !self.directed && from != to {
+            self.adjacency[to as usize].push((from, weight.clone()));
+        }
+        self.adjacency[from as usize].push((to, weight));
+    }
+
+    pub fn remove_edge(&mut self, from: NodeId, to: NodeId) {
+        self.adjacency[from as usize].retain(|(n, _)| *n != to);
+        if !self.directed {
+            self.adjacency[to as usize].retain(|(n, _)| *n != from);
+        }
+    }
+
+    pub fn has_edge(&self, from: NodeId, to: NodeId) -> bool {
+        self.adjacency[from as usize].iter().any(|(n, _)| *n == to)
+    }
+
+    pub fn id(&self, label: &L) -> Option<NodeId> {
+        self.nodes.get(label)
+    }
+
+    pub fn label(&self, id: NodeId) -> &L {
+        self.nodes.label(id)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        let count: usize = self.adjacency.iter().map(|e| e.len()).sum();
+        if self.directed {
+            count
+        } else {
+            let self_loops = self.adjacency.iter().enumerate()
+                .map(|(i, e)| e.iter().filter(|(n, _)| *n as usize == i).count())
+                .sum::<usize>();
+            (count + self_loops) / 2
+        }
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        0..self.nodes.len() as NodeId
+    }
+
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.adjacency[id as usize]
+    }
+
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.adjacency[id as usize].iter().map(|(n, _)| *n)
+    }
+
+    pub fn degree(&self, id: NodeId) -> usize {
+        self.adjacency[id as usize].len()
+    }
+
+    // Every edge once, as (from, to, weight); undirected edges are reported with from <= to
+    pub fn all_edges(&self) -> impl Iterator<Item = (NodeId, NodeId, &W)> + '_ {
+        self.adjacency.iter().enumerate()
+            .flat_map(|(from, edges)| {
+                edges.iter().map(move |(to, w)| (from as NodeId, *to, w))
+            })
+            .filter(|(from, to, _)| self.directed || from <= to)
+    }
+}
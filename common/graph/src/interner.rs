@@ -0,0 +1,51 @@
+use std::{collections::HashMap, hash::Hash};
+
+pub type NodeId = u32;
+
+// Maps node labels (names, points, ...) to dense ids so the graph can be stored in plain Vecs
+#[derive(Debug, Clone)]
+pub struct Interner<L> {
+    labels: Vec<L>,
+    ids: HashMap<L, NodeId>,
+}
+impl<L: Hash + Eq + Clone> Interner<L> {
+    pub fn new() -> Self {
+        Interner { labels: Vec::new(), ids: HashMap::new() }
+    }
+
+    pub fn intern(&mut self, label: L) -> NodeId {
+        if let Some(id) = self.ids.get(&label) {
+            return *id;
+        }
+
+        let id = self.labels.len() as NodeId;
+        self.ids.insert(label.clone(), id);
+        self.labels.push(label);
+        id
+    }
+
+    pub fn get(&self, label: &L) -> Option<NodeId> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: NodeId) -> &L {
+        &self.labels[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn labels(&self) -> &[L] {
+        &self.labels
+    }
+}
+impl<L: Hash + Eq + Clone> Default for Interner<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
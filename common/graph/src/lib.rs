@@ -0,0 +1,131 @@
+mod interner;
+mod graph;
+mod algorithms;
+mod dot;
+
+pub use interner::{Interner, NodeId};
+pub use graph::Graph;
+pub use dot::DotStyle;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_directed(edges: &[(&'static str, &'static str)]) -> Graph<&'static str> {
+        let mut graph = Graph::new_directed();
+        for &(from, to) in edges {
+            let from = graph.add_node(from);
+            let to = graph.add_node(to);
+            graph.add_edge(from, to, ());
+        }
+        graph
+    }
+
+    fn labels(graph: &Graph<&'static str>, ids: &[NodeId]) -> Vec<&'static str> {
+        ids.iter().map(|id| *graph.label(*id)).collect()
+    }
+
+    #[test]
+    fn interner_reuses_ids() {
+        let mut interner = Interner::new();
+        let a = interner.intern("a");
+        let b = interner.intern("b");
+        assert_eq!(interner.intern("a"), a);
+        assert_eq!((a, b), (0, 1));
+        assert_eq!(interner.label(b), &"b");
+        assert_eq!(interner.get(&"c"), None);
+    }
+
+    #[test]
+    fn undirected_edges() {
+        let mut graph: Graph<&str> = Graph::new_undirected();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, c, ());
+        graph.add_edge(c, c, ());
+
+        assert!(graph.has_edge(b, a));
+        assert_eq!(graph.edge_count(), 3);
+
+        graph.remove_edge(b, a);
+        assert!(!graph.has_edge(a, b));
+        assert_eq!(graph.edge_count(), 2);
+    }
+
+    #[test]
+    fn bfs_and_dfs() {
+        let graph = build_directed(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("d", "e")]);
+        let a = graph.id(&"a").unwrap();
+
+        assert_eq!(labels(&graph, &graph.bfs(a)), vec!["a", "b", "c", "d", "e"]);
+        assert_eq!(labels(&graph, &graph.dfs(a)), vec!["a", "b", "d", "e", "c"]);
+
+        let distances = graph.bfs_distances(graph.id(&"b").unwrap());
+        assert_eq!(distances[graph.id(&"e").unwrap() as usize], Some(2));
+        assert_eq!(distances[a as usize], None);
+    }
+
+    #[test]
+    fn connected_components() {
+        let mut graph = build_directed(&[("a", "b"), ("c", "b"), ("d", "e")]);
+        graph.add_node("f");
+
+        let mut sizes: Vec<usize> = graph.connected_components().iter().map(|c| c.len()).collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn topological_sort() {
+        let graph = build_directed(&[("shirt", "tie"), ("tie", "jacket"), ("trousers", "shoes"), ("trousers", "belt"), ("belt", "jacket")]);
+        let order = labels(&graph, &graph.topological_sort().unwrap());
+        let position = |l: &str| order.iter().position(|x| *x == l).unwrap();
+
+        assert!(position("shirt") < position("tie"));
+        assert!(position("tie") < position("jacket"));
+        assert!(position("belt") < position("jacket"));
+        assert!(position("trousers") < position("shoes"));
+
+        let cyclic = build_directed(&[("a", "b"), ("b", "c"), ("c", "a")]);
+        assert_eq!(cyclic.topological_sort(), None);
+    }
+
+    #[test]
+    fn strongly_connected_components() {
+        let graph = build_directed(&[("a", "b"), ("b", "c"), ("c", "a"), ("c", "d"), ("d", "e"), ("e", "d"), ("e", "f")]);
+        let mut components: Vec<Vec<&str>> = graph.strongly_connected_components().iter()
+            .map(|c| {
+                let mut c = labels(&graph, c);
+                c.sort();
+                c
+            })
+            .collect();
+
+        // Sinks come out first
+        assert_eq!(components[0], vec!["f"]);
+
+        components.sort();
+        assert_eq!(components, vec![vec!["a", "b", "c"], vec!["d", "e"], vec!["f"]]);
+    }
+
+    #[test]
+    fn dot_export() {
+        let mut graph: Graph<&str, usize> = Graph::new_undirected();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b \"quoted\"");
+        graph.add_edge(a, b, 12);
+
+        let dot = graph.to_dot("test",
+            |l| if *l == "a" { DotStyle::new("box", "green") } else { DotStyle::default() },
+            |w| Some(w.to_string()));
+
+        assert_eq!(dot, concat!(
+            "graph \"test\" {\n",
+            "    \"a\" [shape=box, style=filled, fillcolor=\"green\"];\n",
+            "    \"b \\\"quoted\\\"\";\n",
+            "    \"a\" -- \"b \\\"quoted\\\"\" [label=\"12\"];\n",
+            "}\n"));
+    }
+}
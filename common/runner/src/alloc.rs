@@ -0,0 +1,160 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::{
+        atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering::Relaxed},
+        OnceLock,
+    },
+};
+
+// Binaries opt in with install_counting_allocator!(). Until --alloc-stats (or measure_allocs)
+// turns counting on it only passes calls through to the system allocator.
+pub struct CountingAllocator;
+
+// install_counting_allocator!() - makes CountingAllocator the calling binary's global allocator
+#[macro_export]
+macro_rules! install_counting_allocator {
+    () => {
+        #[global_allocator]
+        static COUNTING_ALLOCATOR : $crate::CountingAllocator = $crate::CountingAllocator;
+    };
+}
+
+static COUNTING : AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS : AtomicUsize = AtomicUsize::new(0);
+static BYTES : AtomicUsize = AtomicUsize::new(0);
+// Heap allocated minus heap freed while counting was on. Memory allocated before counting started
+// can be freed during it, so this can go below zero.
+static CURRENT : AtomicIsize = AtomicIsize::new(0);
+static PEAK : AtomicIsize = AtomicIsize::new(0);
+
+impl CountingAllocator {
+    fn record(grown_by: usize, size: usize) {
+        if COUNTING.load(Relaxed) {
+            let current = CURRENT.fetch_add(grown_by as isize, Relaxed) + grown_by as isize;
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            BYTES.fetch_add(size, Relaxed);
+            PEAK.fetch_max(current, Relaxed);
+        }
+    }
+
+    fn release(size: usize) {
+        if COUNTING.load(Relaxed) {
+            CURRENT.fetch_sub(size as isize, Relaxed);
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size(), layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size(), layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::release(layout.size());
+    }
+
+    // A realloc counts as one allocation of the new size
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size >= layout.size() {
+                Self::record(new_size - layout.size(), new_size);
+            } else {
+                Self::release(layout.size() - new_size);
+                Self::record(0, new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+// What a stage allocated: how many allocations, their total size, and the most heap it added on
+// top of what was already live when it started
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak: usize,
+}
+
+// Turns counting on when the binary was run with --alloc-stats, as long as it installed the
+// counting allocator
+pub fn alloc_stats_enabled() -> bool {
+    static ENABLED : OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        if !std::env::args().any(|a| a == "--alloc-stats") {
+            return false;
+        }
+        if !counting_allocator_installed() {
+            eprintln!("warning: --alloc-stats needs aoc_runner::install_counting_allocator!() in this binary");
+            return false;
+        }
+        COUNTING.store(true, Relaxed);
+        true
+    })
+}
+
+// Only the counting allocator ever counts anything
+fn counting_allocator_installed() -> bool {
+    let (_, stats) = measure_allocs(|| std::hint::black_box(Box::new(0u64)));
+    stats.allocations > 0
+}
+
+// Runs f with counting on and returns what it allocated. Stages can nest, the outer stage's
+// peak still covers everything that ran inside it. Without the counting allocator installed
+// everything comes back as zero.
+pub fn measure_allocs<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let was_counting = COUNTING.swap(true, Relaxed);
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let bytes = BYTES.load(Relaxed);
+    let start = CURRENT.load(Relaxed);
+    let outer_peak = PEAK.swap(start, Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        bytes: BYTES.load(Relaxed) - bytes,
+        peak: (PEAK.load(Relaxed) - start).max(0) as usize,
+    };
+    PEAK.fetch_max(outer_peak, Relaxed);
+    COUNTING.store(was_counting, Relaxed);
+
+    (result, stats)
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS : [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 { format!("{bytes} B") } else { format!("{value:.1} {}", UNITS[unit]) }
+}
+
+// Runs a stage and, with --alloc-stats, prints what it allocated
+pub fn alloc_stage<T>(stage: &str, f: impl FnOnce() -> T) -> T {
+    if !alloc_stats_enabled() {
+        return f();
+    }
+
+    let (result, stats) = measure_allocs(f);
+    println!("Alloc {stage}: {} allocations, {} allocated, {} peak heap",
+        stats.allocations, format_bytes(stats.bytes), format_bytes(stats.peak));
+    result
+}
@@ -0,0 +1,162 @@
+use std::{cmp::Ordering, fmt};
+
+use num_bigint::{BigInt, BigUint};
+use serde::{Serialize, Serializer};
+
+use crate::{ocr, Grid};
+
+// What a puzzle part produces. Numbers compare by value whatever their width, so a part can
+// return an i64 and its test can check it against a u128 or a plain literal.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i128),
+    Unsigned(u128),
+    Big(BigInt),
+    Text(String),
+    // Letters drawn with '#' and '.', as some puzzles answer with. Shown and compared as the
+    // letters it spells when they can be read.
+    Art(String),
+}
+impl Answer {
+    pub fn art(lines: &[String]) -> Answer {
+        Answer::Art(lines.join("\n"))
+    }
+
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Answer::Signed(v) => Some(BigInt::from(*v)),
+            Answer::Unsigned(v) => Some(BigInt::from(*v)),
+            Answer::Big(v) => Some(v.clone()),
+            Answer::Text(_) | Answer::Art(_) => None,
+        }
+    }
+
+    pub fn as_i128(&self) -> Option<i128> {
+        self.to_bigint().and_then(|v| i128::try_from(v).ok())
+    }
+
+    // The letters an Art answer spells
+    pub fn decoded(&self) -> Option<String> {
+        match self {
+            Answer::Art(v) => ocr::decode_text(v),
+            _ => None,
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        self.to_bigint().is_some()
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Signed(v) => write!(f, "{v}"),
+            Answer::Unsigned(v) => write!(f, "{v}"),
+            Answer::Big(v) => write!(f, "{v}"),
+            Answer::Text(v) => write!(f, "{v}"),
+            Answer::Art(v) => match self.decoded() {
+                Some(letters) => write!(f, "{letters}"),
+                None => write!(f, "\n{v}"),
+            },
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.to_bigint(), other.to_bigint()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => self.to_string() == other.to_string(),
+            _ => false,
+        }
+    }
+}
+impl Eq for Answer {}
+
+// Numbers order by value and before any text
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Answer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.to_bigint(), other.to_bigint()) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => self.to_string().cmp(&other.to_string()),
+        }
+    }
+}
+
+// Only one integer type so `assert_eq!(result, 1234)` can infer the literal's type
+impl PartialEq<i64> for Answer {
+    fn eq(&self, other: &i64) -> bool {
+        self.to_bigint() == Some(BigInt::from(*other))
+    }
+}
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Answer::Text(v) => v == other,
+            Answer::Art(v) => v == other || self.decoded().is_some_and(|d| d == *other),
+            _ => self.to_bigint().is_some_and(|v| v.to_string() == *other),
+        }
+    }
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => { $(impl From<$t> for Answer { fn from(v: $t) -> Self { Answer::Signed(v as i128) } })* };
+}
+macro_rules! from_unsigned {
+    ($($t:ty),*) => { $(impl From<$t> for Answer { fn from(v: $t) -> Self { Answer::Unsigned(v as u128) } })* };
+}
+from_signed!(i8, i16, i32, i64, i128, isize);
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl From<BigInt> for Answer {
+    fn from(v: BigInt) -> Self {
+        Answer::Big(v)
+    }
+}
+impl From<BigUint> for Answer {
+    fn from(v: BigUint) -> Self {
+        Answer::Big(BigInt::from(v))
+    }
+}
+impl From<String> for Answer {
+    fn from(v: String) -> Self {
+        Answer::Text(v)
+    }
+}
+impl From<&Grid<bool>> for Answer {
+    fn from(v: &Grid<bool>) -> Self {
+        Answer::Art(v.to_string())
+    }
+}
+impl From<&str> for Answer {
+    fn from(v: &str) -> Self {
+        Answer::Text(v.to_string())
+    }
+}
+
+// Numbers that fit in 64 bits stay JSON numbers, anything wider becomes a string so it survives
+// parsers that read numbers as doubles
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.to_bigint() {
+            Some(v) => match (i64::try_from(&v), u64::try_from(&v)) {
+                (Ok(v), _) => serializer.serialize_i64(v),
+                (_, Ok(v)) => serializer.serialize_u64(v),
+                _ => serializer.serialize_str(&v.to_string()),
+            },
+            None => match (self, self.decoded()) {
+                (_, Some(letters)) => serializer.serialize_str(&letters),
+                (Answer::Art(v), None) => serializer.serialize_str(v),
+                (other, None) => serializer.serialize_str(&other.to_string()),
+            },
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::{fmt::Display, fs, io, path::Path, time::{SystemTime, UNIX_EPOCH}};
+
+// Never submit twice for the same day within this many seconds, even if the site would allow it
+pub const MIN_SUBMIT_INTERVAL_SECS : u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    // Produced by a run, never submitted or confirmed
+    Unchecked,
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    // The site refused to look at the answer, we had to wait longer
+    RateLimited,
+    AlreadySolved,
+}
+impl Outcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Unchecked => "unchecked",
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Incorrect => "incorrect",
+            Outcome::RateLimited => "rate_limited",
+            Outcome::AlreadySolved => "already_solved",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Outcome> {
+        match s {
+            "unchecked" => Some(Outcome::Unchecked),
+            "correct" => Some(Outcome::Correct),
+            "too_high" => Some(Outcome::TooHigh),
+            "too_low" => Some(Outcome::TooLow),
+            "incorrect" => Some(Outcome::Incorrect),
+            "rate_limited" => Some(Outcome::RateLimited),
+            "already_solved" => Some(Outcome::AlreadySolved),
+            _ => None,
+        }
+    }
+
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect)
+    }
+}
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Outcome::Unchecked => "unchecked",
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Incorrect => "incorrect",
+            Outcome::RateLimited => "rate limited",
+            Outcome::AlreadySolved => "already solved",
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerRecord {
+    pub timestamp: u64,
+    pub part: u32,
+    pub answer: String,
+    pub outcome: Outcome,
+    // Cooldown the site asked for, in seconds
+    pub retry_after: u64,
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+// Backslash, newline and tab as \\, \n and \t, so an answer always fits in the last field of a line
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n").replace('\t', "\\t")
+}
+
+fn unescape(answer: &str) -> String {
+    let mut out = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+// Every answer a day has produced or submitted, one per line:
+// timestamp, part, outcome, retry_after, answer (tab separated, answer last)
+// Answers that are undecoded art span several lines, so the answer is stored escaped.
+#[derive(Debug, Default, Clone)]
+pub struct AnswerLog {
+    pub records: Vec<AnswerRecord>,
+}
+impl AnswerLog {
+    pub fn parse(contents: &str) -> AnswerLog {
+        let records = contents.lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(5, '\t');
+                Some(AnswerRecord {
+                    timestamp: parts.next()?.parse().ok()?,
+                    part: parts.next()?.parse().ok()?,
+                    outcome: Outcome::parse(parts.next()?)?,
+                    retry_after: parts.next()?.parse().ok()?,
+                    answer: unescape(parts.next()?),
+                })
+            })
+            .collect();
+
+        AnswerLog { records }
+    }
+
+    pub fn load(path: &Path) -> AnswerLog {
+        AnswerLog::parse(&fs::read_to_string(path).unwrap_or_default())
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let contents : String = self.records.iter()
+            .map(|s| format!("{}\t{}\t{}\t{}\t{}\n", s.timestamp, s.part, s.outcome.as_str(), s.retry_after, escape(&s.answer)))
+            .collect();
+        fs::write(path, contents)
+    }
+
+    pub fn push(&mut self, record: AnswerRecord) {
+        self.records.push(record);
+    }
+
+    pub fn correct_answer(&self, part: u32) -> Option<&str> {
+        self.records.iter()
+            .find(|s| s.part == part && s.outcome == Outcome::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    pub fn wrong_guesses(&self, part: u32) -> impl Iterator<Item = &AnswerRecord> {
+        self.records.iter().filter(move |s| s.part == part && s.outcome.is_wrong())
+    }
+
+    // A previous verdict for this answer, if the site (or we) ever gave one
+    pub fn find_guess(&self, part: u32, answer: &str) -> Option<&AnswerRecord> {
+        self.records.iter()
+            .find(|s| s.part == part && s.answer == answer
+                && !matches!(s.outcome, Outcome::RateLimited | Outcome::Unchecked))
+    }
+
+    // Earliest time (unix seconds) we're willing to submit again
+    pub fn next_allowed_submit(&self) -> u64 {
+        self.records.iter()
+            .filter(|s| s.outcome != Outcome::Unchecked)
+            .map(|s| s.timestamp + s.retry_after.max(MIN_SUBMIT_INTERVAL_SECS))
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Records an answer a run produced, unless we've already seen it
+    pub fn record_produced(&mut self, part: u32, answer: &str) -> bool {
+        if self.records.iter().any(|s| s.part == part && s.answer == answer) {
+            return false;
+        }
+
+        self.push(AnswerRecord { timestamp: now_secs(), part, answer: answer.to_string(), outcome: Outcome::Unchecked, retry_after: 0 });
+        true
+    }
+
+    // Exclusive (lower, upper) bounds implied by "too low" and "too high" verdicts
+    pub fn bounds(&self, part: u32) -> (Option<i128>, Option<i128>) {
+        let guesses = |outcome: Outcome| {
+            self.wrong_guesses(part)
+                .filter(move |s| s.outcome == outcome)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        (guesses(Outcome::TooLow).max(), guesses(Outcome::TooHigh).min())
+    }
+
+    // Reasons to doubt a freshly produced answer, based on everything we know about this part
+    pub fn check(&self, part: u32, answer: &str) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if let Some(correct) = self.correct_answer(part) {
+            if correct != answer {
+                warnings.push(format!("part {part}: {answer} differs from the confirmed answer {correct}"));
+            }
+            return warnings;
+        }
+
+        if let Some(guess) = self.find_guess(part, answer) {
+            if guess.outcome.is_wrong() {
+                warnings.push(format!("part {part}: {answer} was already rejected ({})", guess.outcome));
+                return warnings;
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let (lower, upper) = self.bounds(part);
+            if let Some(lower) = lower.filter(|l| value <= *l) {
+                warnings.push(format!("part {part}: {answer} is not above {lower}, which was too low"));
+            }
+            if let Some(upper) = upper.filter(|u| value >= *u) {
+                warnings.push(format!("part {part}: {answer} is not below {upper}, which was too high"));
+            }
+        }
+
+        warnings
+    }
+}
@@ -0,0 +1,25 @@
+// A property of the real puzzle inputs that a solution relies on but the puzzle never promises.
+// The check explains why it fails so the input can be looked at.
+pub struct Assumption<T: ?Sized> {
+    pub name: &'static str,
+    pub check: fn(&T) -> Result<(), String>,
+}
+
+// The failures for this input, one line per assumption that does not hold
+pub fn failed_assumptions<T: ?Sized>(input: &T, assumptions: &[Assumption<T>]) -> Vec<String> {
+    assumptions.iter()
+        .filter_map(|a| (a.check)(input).err().map(|why| {
+            format!("assumption '{}' does not hold for this input: {why}", a.name)
+        }))
+        .collect()
+}
+
+// Run before solving a part: prints every assumption that fails, and the part should only be
+// solved when this returns true, as its answer would be wrong otherwise
+pub fn check_assumptions<T: ?Sized>(part: u32, input: &T, assumptions: &[Assumption<T>]) -> bool {
+    let failures = failed_assumptions(input, assumptions);
+    for failure in &failures {
+        println!("Part {part}: {failure}");
+    }
+    failures.is_empty()
+}
@@ -0,0 +1,79 @@
+use std::{env, fs, path::PathBuf};
+
+pub const DEFAULT_BASE_URL : &str = "https://adventofcode.com";
+
+// Settings come from a `key = value` config file, then AOC_* environment variables override them
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub inputs_dir: PathBuf,
+}
+impl Config {
+    pub fn workspace_root() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..")
+    }
+
+    pub fn default_path() -> Option<PathBuf> {
+        match env::var("AOC_CONFIG") {
+            Ok(path) => Some(PathBuf::from(path)),
+            Err(_) => env::var("HOME").ok().map(|home| PathBuf::from(home).join(".config/aoc/config")),
+        }
+    }
+
+    pub fn load() -> Config {
+        let contents = Config::default_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .unwrap_or_default();
+
+        let mut config = Config::parse(&contents);
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session.trim().to_string());
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = base_url.trim_end_matches('/').to_string();
+        }
+        if let Ok(inputs_dir) = env::var("AOC_INPUTS_DIR") {
+            config.inputs_dir = PathBuf::from(inputs_dir);
+        }
+        config
+    }
+
+    pub fn parse(contents: &str) -> Config {
+        let mut config = Config {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            inputs_dir: Config::workspace_root().join("inputs"),
+        };
+
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else { continue; };
+            let value = value.trim().trim_matches('"').to_string();
+            match key.trim() {
+                "session" => { config.session = Some(value); },
+                "base_url" => { config.base_url = value.trim_end_matches('/').to_string(); },
+                "inputs_dir" => { config.inputs_dir = PathBuf::from(value); },
+                _ => {}
+            }
+        }
+
+        config
+    }
+
+    pub fn day_dir(&self, year: u32) -> PathBuf {
+        self.inputs_dir.join(year.to_string())
+    }
+
+    pub fn input_path(&self, year: u32, day: u32) -> PathBuf {
+        self.day_dir(year).join(format!("day{day}.txt"))
+    }
+
+    pub fn answers_path(&self, year: u32, day: u32) -> PathBuf {
+        self.day_dir(year).join(format!("day{day}.answers"))
+    }
+}
@@ -0,0 +1,174 @@
+use std::{fs, path::PathBuf, sync::Mutex};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+// Named intermediate results a solver wants to show alongside its answer
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Artifacts {
+    entries: Vec<(String, Value)>,
+}
+impl Artifacts {
+    pub fn new() -> Self {
+        Artifacts::default()
+    }
+
+    pub fn add<T: Serialize + ?Sized>(&mut self, name: &str, value: &T) {
+        let value = serde_json::to_value(value).expect("artifact is not serializable");
+        match self.entries.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((name.to_string(), value)),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.entries.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.entries.iter().map(|(n, v)| (n.as_str(), v))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn to_json(&self) -> Value {
+        Value::Object(self.entries.iter().cloned().collect())
+    }
+}
+
+// A solver's result together with the artifacts that explain it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explained<T> {
+    pub result: T,
+    pub artifacts: Artifacts,
+}
+impl<T> Explained<T> {
+    pub fn new(result: T) -> Self {
+        Explained { result, artifacts: Artifacts::new() }
+    }
+
+    pub fn with<V: Serialize + ?Sized>(mut self, name: &str, value: &V) -> Self {
+        self.artifacts.add(name, value);
+        self
+    }
+}
+
+// --explain prints artifacts after each answer, --explain-json <file> collects them into a file
+#[derive(Debug, Default, PartialEq)]
+pub struct ExplainOptions {
+    pub display: bool,
+    pub json_path: Option<PathBuf>,
+}
+impl ExplainOptions {
+    pub fn from_args<I: Iterator<Item = String>>(args: I) -> Self {
+        let mut options = ExplainOptions::default();
+        let mut args = args;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--explain" => { options.display = true; },
+                "--explain-json" => { options.json_path = args.next().map(PathBuf::from); },
+                _ => {}
+            }
+        }
+        options
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.display || self.json_path.is_some()
+    }
+}
+
+static EXPORTED : Mutex<Option<Map<String, Value>>> = Mutex::new(None);
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Array(_) | Value::Object(_) => None,
+        other => Some(other.to_string()),
+    }
+}
+
+// Lists of flat records (e.g. one per hand or per ghost) read best as a table
+fn format_table(rows: &[Value]) -> Option<Vec<String>> {
+    let Value::Object(first) = rows.first()? else { return None; };
+    let columns : Vec<&String> = first.keys().collect();
+
+    let mut cells = vec![ columns.iter().map(|c| c.to_string()).collect::<Vec<String>>() ];
+    for row in rows {
+        let Value::Object(row) = row else { return None; };
+        let row : Option<Vec<String>> = columns.iter()
+            .map(|c| row.get(*c).map(scalar_text).unwrap_or(Some(String::new())))
+            .collect();
+        cells.push(row?);
+    }
+
+    let widths : Vec<usize> = (0..columns.len())
+        .map(|i| cells.iter().map(|row| row[i].len()).max().unwrap())
+        .collect();
+
+    Some(cells.iter()
+        .map(|row| row.iter().zip(&widths)
+            .map(|(cell, width)| format!("{cell:>width$}"))
+            .collect::<Vec<String>>()
+            .join("  "))
+        .collect())
+}
+
+// Short values stay on the artifact's line, tables and anything bigger go underneath it
+pub fn format_artifacts(part: u32, artifacts: &Artifacts) -> String {
+    let mut text = format!("Part {part} explained:\n");
+    for (name, value) in artifacts.iter() {
+        let compact = value.to_string();
+        let table = match value {
+            Value::Array(rows) => format_table(rows),
+            _ => None,
+        };
+
+        if let Some(table) = table {
+            text += &format!("  {name}:\n");
+            for line in table {
+                text += &format!("    {line}\n");
+            }
+        } else if compact.len() <= 80 {
+            text += &format!("  {name}: {compact}\n");
+        } else {
+            text += &format!("  {name}:\n");
+            for line in serde_json::to_string_pretty(value).unwrap().lines() {
+                text += &format!("    {line}\n");
+            }
+        }
+    }
+    text
+}
+
+pub fn report_explanation(part: u32, artifacts: &Artifacts) {
+    let options = ExplainOptions::from_args(std::env::args());
+
+    if options.display {
+        print!("{}", format_artifacts(part, artifacts));
+    }
+
+    if let Some(path) = options.json_path {
+        // Rewrite the whole file each time so it always holds every part reported so far
+        let mut exported = EXPORTED.lock().unwrap();
+        let parts = exported.get_or_insert_with(Map::new);
+        parts.insert(format!("part_{part}"), artifacts.to_json());
+
+        let json = serde_json::to_string_pretty(&Value::Object(parts.clone())).unwrap();
+        if let Err(e) = fs::write(&path, json + "\n") {
+            eprintln!("warning: failed to write {}: {e}", path.display());
+        }
+    }
+}
+
+// explain!(1, explained) - reports the answer, then its artifacts if --explain was asked for
+#[macro_export]
+macro_rules! explain {
+    ($part:expr, $explained:expr) => {{
+        let explained = $crate::alloc_stage(&format!("part {}", $part), || $explained);
+        $crate::report_answer(env!("CARGO_PKG_NAME"), $part, &$crate::Answer::from(explained.result));
+        $crate::report_explanation($part, &explained.artifacts);
+    }};
+}
@@ -0,0 +1,44 @@
+use std::borrow::Cow;
+
+use crate::{arg_value, input_path};
+
+// `--generate <size>` asks a day binary to print a generated input of that size instead of
+// solving, so `aoc scale` can time it on inputs of increasing size
+pub fn generate_size() -> Option<usize> {
+    let size = arg_value("--generate")?;
+    Some(size.parse().unwrap_or_else(|_| panic!("invalid size '{size}'")))
+}
+
+// The puzzle input for days that need all of it at once: the `--input <path>` file if one was
+// given, otherwise the embedded input.txt
+pub fn read_input(embedded: &'static str) -> Cow<'static, str> {
+    match input_path() {
+        Some(path) => Cow::Owned(std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Failed to read {}: {e}", path.display()))),
+        None => Cow::Borrowed(embedded),
+    }
+}
+
+// Small deterministic generator (xorshift64*) so a size always produces the same input
+pub struct Rng(u64);
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+
+    // Uniform enough in 0..n for generating puzzle inputs
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+}
@@ -0,0 +1,83 @@
+use std::{fmt, ops::Index};
+
+// A rectangular grid of cells stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+}
+impl<T> Grid<T> {
+    // Each line becomes a row. Short lines are not padded, so every line must be the same length.
+    pub fn parse(text: &str, f: impl Fn(char) -> T) -> Self {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        for line in text.lines() {
+            let row_start = cells.len();
+            cells.extend(line.chars().map(&f));
+            let row_width = cells.len() - row_start;
+            if height == 0 {
+                width = row_width;
+            }
+            assert_eq!(row_width, width, "Row {height} is {row_width} wide, expected {width}");
+            height += 1;
+        }
+        Grid { width, height, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height { self.cells.get(y * self.width + x) } else { None }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        assert!(x < self.width && y < self.height, "({x}, {y}) is outside the grid");
+        self.cells[y * self.width + x] = value;
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() panics on 0, an empty grid simply has no rows
+        self.cells.chunks(self.width.max(1))
+    }
+}
+// grid[(x, y)]
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).unwrap_or_else(|| panic!("({x}, {y}) is outside the grid"))
+    }
+}
+
+impl Grid<bool> {
+    pub fn to_text(&self, on: char, off: char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(|&c| if c { on } else { off }).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+impl fmt::Display for Grid<bool> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_text('#', '.'))
+    }
+}
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows : Vec<String> = self.rows().map(|row| row.iter().collect()).collect();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
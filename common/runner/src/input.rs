@@ -0,0 +1,53 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+// Large buffer so multi-GB generated inputs aren't bottlenecked on read calls
+const READ_BUFFER_SIZE : usize = 1 << 20;
+
+// The argument after `name` (e.g. `arg_value("--rules")` for `--rules <file>`), if the flag was
+// given. A flag given without a value is a mistake, not a reason to fall back to the default.
+pub fn arg_value(name: &str) -> Option<String> {
+    let mut args = std::env::args().skip_while(|a| a != name);
+    args.next()?;
+    Some(args.next().unwrap_or_else(|| panic!("{name} needs a value")))
+}
+
+// `--input <path>` streams the puzzle input from a file instead of the embedded input.txt
+pub fn input_path() -> Option<PathBuf> {
+    arg_value("--input").map(PathBuf::from)
+}
+
+pub fn open_input(path: &Path) -> BufReader<File> {
+    let file = File::open(path).unwrap_or_else(|e| panic!("Failed to open {}: {e}", path.display()));
+    BufReader::with_capacity(READ_BUFFER_SIZE, file)
+}
+
+// Calls `f` with each `delimiter`-separated record (without the delimiter or a trailing newline),
+// reusing one buffer so memory stays bounded by the longest record
+pub fn for_each_record<R: BufRead>(mut reader: R, delimiter: u8, mut f: impl FnMut(&str)) {
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        let read = reader.read_until(delimiter, &mut buffer).expect("Failed to read input");
+        if read == 0 {
+            break;
+        }
+
+        if buffer.last() == Some(&delimiter) {
+            buffer.pop();
+        }
+        while matches!(buffer.last(), Some(b'\n') | Some(b'\r')) {
+            buffer.pop();
+        }
+
+        f(std::str::from_utf8(&buffer).expect("Input is not valid UTF-8"));
+    }
+}
+
+// Streaming equivalent of str::lines()
+pub fn for_each_line<R: BufRead>(reader: R, f: impl FnMut(&str)) {
+    for_each_record(reader, b'\n', f);
+}
@@ -0,0 +1,262 @@
+mod config;
+mod answers;
+mod report;
+mod explain;
+mod input;
+mod generate;
+mod answer;
+mod grid;
+mod alloc;
+mod snapshot;
+mod assume;
+pub mod ocr;
+
+pub use config::{Config, DEFAULT_BASE_URL};
+pub use answers::{now_secs, AnswerLog, AnswerRecord, Outcome, MIN_SUBMIT_INTERVAL_SECS};
+pub use report::{parse_package_name, report_answer, timed};
+pub use explain::{format_artifacts, report_explanation, Artifacts, ExplainOptions, Explained};
+pub use input::{arg_value, for_each_line, for_each_record, input_path, open_input};
+pub use generate::{generate_size, read_input, Rng};
+pub use answer::Answer;
+pub use grid::Grid;
+pub use alloc::{alloc_stage, alloc_stats_enabled, format_bytes, measure_allocs, AllocStats, CountingAllocator};
+pub use snapshot::{assert_snapshot, check_snapshot, diff_lines, pending_path, snapshot_path, SnapshotResult, PENDING_EXTENSION, SNAPSHOT_EXTENSION};
+pub use assume::{check_assumptions, failed_assumptions, Assumption};
+pub use serde_json::{json, Value};
+
+#[cfg(test)]
+install_counting_allocator!();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(timestamp: u64, part: u32, answer: &str, outcome: Outcome, retry_after: u64) -> AnswerRecord {
+        AnswerRecord { timestamp, part, answer: answer.to_string(), outcome, retry_after }
+    }
+
+    #[test]
+    fn config_file() {
+        let config = Config::parse("# comment\nsession = \"abc123\"\nbase_url = http://127.0.0.1:8080/\ninputs_dir=/tmp/aoc\n");
+        assert_eq!(config.session.as_deref(), Some("abc123"));
+        assert_eq!(config.base_url, "http://127.0.0.1:8080");
+        assert_eq!(config.input_path(2023, 7), std::path::PathBuf::from("/tmp/aoc/2023/day7.txt"));
+
+        let config = Config::parse("");
+        assert_eq!(config.session, None);
+        assert_eq!(config.base_url, DEFAULT_BASE_URL);
+    }
+
+    #[test]
+    fn answer_log_round_trip() {
+        let mut log = AnswerLog::default();
+        log.push(record(100, 1, "42", Outcome::TooLow, 60));
+        log.push(record(200, 1, "50", Outcome::TooHigh, 300));
+        log.push(record(600, 1, "47", Outcome::Correct, 0));
+
+        let path = std::env::temp_dir().join(format!("aoc_runner_log_{}.answers", std::process::id()));
+        log.save(&path).unwrap();
+        let loaded = AnswerLog::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.records, log.records);
+        assert_eq!(loaded.correct_answer(1), Some("47"));
+        assert_eq!(loaded.correct_answer(2), None);
+        assert_eq!(loaded.wrong_guesses(1).count(), 2);
+        assert_eq!(loaded.find_guess(1, "50").map(|s| s.outcome), Some(Outcome::TooHigh));
+        assert_eq!(loaded.next_allowed_submit(), 660);
+    }
+
+    #[test]
+    fn art_answer_log_round_trip() {
+        // Art the OCR can't read is logged as the picture, which spans several lines
+        let art = Answer::art(&["#.#".to_string(), "###".to_string(), "#.#".to_string()]).to_string();
+        assert_eq!(art, "\n#.#\n###\n#.#");
+
+        let mut log = AnswerLog::default();
+        log.push(record(100, 1, &art, Outcome::Unchecked, 0));
+        log.push(record(200, 1, "back\\slash\ttab\\n", Outcome::Incorrect, 0));
+        log.push(record(300, 2, "7", Outcome::Correct, 0));
+
+        let path = std::env::temp_dir().join(format!("aoc_runner_art_{}.answers", std::process::id()));
+        log.save(&path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let mut loaded = AnswerLog::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(contents.lines().count(), 3);
+        assert_eq!(loaded.records, log.records);
+        assert!(!loaded.record_produced(1, &art));
+    }
+
+    #[test]
+    fn answer_checks() {
+        let mut log = AnswerLog::default();
+        log.push(record(100, 2, "1000", Outcome::TooHigh, 60));
+        log.push(record(200, 2, "200", Outcome::TooLow, 60));
+        log.push(record(300, 2, "300", Outcome::Incorrect, 60));
+
+        assert_eq!(log.bounds(2), (Some(200), Some(1000)));
+        assert!(log.check(2, "500").is_empty());
+        assert_eq!(log.check(2, "300"), vec!["part 2: 300 was already rejected (incorrect)"]);
+        assert_eq!(log.check(2, "1500"), vec!["part 2: 1500 is not below 1000, which was too high"]);
+        assert_eq!(log.check(2, "150"), vec!["part 2: 150 is not above 200, which was too low"]);
+        assert!(log.check(1, "1500").is_empty());
+
+        assert!(log.record_produced(2, "500"));
+        assert!(!log.record_produced(2, "500"));
+        assert_eq!(log.find_guess(2, "500"), None);
+        assert_eq!(log.next_allowed_submit(), 360);
+
+        log.push(record(400, 2, "450", Outcome::Correct, 0));
+        assert_eq!(log.check(2, "500"), vec!["part 2: 500 differs from the confirmed answer 450"]);
+        assert!(log.check(2, "450").is_empty());
+    }
+
+    #[test]
+    fn package_names() {
+        assert_eq!(parse_package_name("aoc2023_day20"), Some((2023, 20)));
+        assert_eq!(parse_package_name("aoc2022_day1"), Some((2022, 1)));
+        assert_eq!(parse_package_name("aoc_graph"), None);
+    }
+
+    #[test]
+    fn explain_artifacts() {
+        let explained = Explained::new(42)
+            .with("steps", &vec![2, 6])
+            .with("start", "AAA")
+            .with("steps", &vec![3]);
+        assert_eq!(explained.artifacts.get("steps"), Some(&json!([3])));
+        assert_eq!(explained.artifacts.to_json(), json!({ "steps": [3], "start": "AAA" }));
+        assert_eq!(format_artifacts(2, &explained.artifacts), "Part 2 explained:\n  steps: [3]\n  start: \"AAA\"\n");
+
+        let args = ["day", "--explain", "--explain-json", "out.json"].iter().map(|a| a.to_string());
+        let options = ExplainOptions::from_args(args);
+        assert!(options.display && options.enabled());
+        assert_eq!(options.json_path, Some(std::path::PathBuf::from("out.json")));
+        assert!(!ExplainOptions::from_args(std::iter::empty()).enabled());
+
+        let mut table = Artifacts::new();
+        table.add("ghosts", &json!([ { "start": "11A", "steps": 2 }, { "start": "22A", "steps": 3 } ]));
+        assert_eq!(format_artifacts(2, &table), "Part 2 explained:\n  ghosts:\n    start  steps\n      11A      2\n      22A      3\n");
+    }
+
+    #[test]
+    fn streamed_records() {
+        let mut lines = Vec::new();
+        for_each_line("a\r\n\nb c\nd".as_bytes(), |l| lines.push(l.to_string()));
+        assert_eq!(lines, vec!["a", "", "b c", "d"]);
+        assert_eq!(lines, "a\r\n\nb c\nd".lines().collect::<Vec<&str>>());
+
+        let mut records = Vec::new();
+        for_each_record("rn=1,cm-,qp=3\n".as_bytes(), b',', |r| records.push(r.to_string()));
+        assert_eq!(records, vec!["rn=1", "cm-", "qp=3"]);
+    }
+
+    #[test]
+    fn answers() {
+        assert_eq!(Answer::from(42u8), 42);
+        assert_eq!(Answer::from(-7i32), Answer::from(-7i128));
+        assert_eq!(Answer::from(5usize), Answer::Signed(5));
+        assert_ne!(Answer::from(5u64), Answer::from("5"));
+        assert!(Answer::Signed(-1) < Answer::Unsigned(0));
+
+        let big = num_bigint::BigInt::from(u128::MAX) * 4u32;
+        assert_eq!(Answer::from(big.clone()).to_string(), "1361129467683753853853498429727072845820");
+        assert_eq!(Answer::from(u128::MAX).as_i128(), None);
+
+        assert_eq!(Answer::from("JXFP"), "JXFP");
+        let art = Answer::art(&["#..#".to_string(), "####".to_string()]);
+        assert_eq!(art.decoded(), None);
+        assert_eq!(art, "#..#\n####");
+        assert_eq!(format!("Part 2: {art}"), "Part 2: \n#..#\n####");
+
+        assert_eq!(serde_json::to_value(Answer::from(12u64)).unwrap(), json!(12));
+        assert_eq!(serde_json::to_value(Answer::from(-12i32)).unwrap(), json!(-12));
+        assert_eq!(serde_json::to_value(Answer::from(u64::MAX as u128 + 1)).unwrap(), json!("18446744073709551616"));
+        assert_eq!(serde_json::to_value(Answer::from(big)).unwrap(), json!("1361129467683753853853498429727072845820"));
+        assert_eq!(serde_json::to_value(Answer::from("abc")).unwrap(), json!("abc"));
+    }
+
+    #[test]
+    fn ocr_letters() {
+        let small = "\
+            ..........................\n\
+            .####.###...##..#..#.#...#\n\
+            .#....#..#.#..#.#.#..#...#\n\
+            .###..###..#....##....#.#.\n\
+            .#....#..#.#....#.#....#..\n\
+            .#....#..#.#..#.#.#....#..\n\
+            .####.###...##..#..#...#..\n";
+        assert_eq!(ocr::decode_text(small), Some("EBCKY".to_string()));
+
+        let grid = Grid::parse(small, |c| c == '#');
+        assert_eq!((grid.width(), grid.height()), (26, 7));
+        assert!(grid[(1, 1)] && !grid[(0, 1)]);
+        assert_eq!(ocr::decode(&grid), Some("EBCKY".to_string()));
+        assert_eq!(ocr::decode_chars(&Grid::parse(small, |c| c)), Some("EBCKY".to_string()));
+
+        let answer = Answer::from(&grid);
+        assert_eq!(answer, "EBCKY");
+        assert_eq!(answer, Answer::from("EBCKY"));
+        assert_eq!(answer.to_string(), "EBCKY");
+        assert_eq!(serde_json::to_value(&answer).unwrap(), json!("EBCKY"));
+
+        let large = "\
+            #....#..#....#\n\
+            #....#..##...#\n\
+            .#..#...##...#\n\
+            .#..#...#.#..#\n\
+            ..##....#.#..#\n\
+            ..##....#..#.#\n\
+            .#..#...#..#.#\n\
+            .#..#...#...##\n\
+            #....#..#...##\n\
+            #....#..#....#";
+        assert_eq!(ocr::decode_text(large), Some("XN".to_string()));
+
+        // Blank rows around the letters are ignored, however tall the canvas
+        let padded = format!("{}{small}{}", "..........................\n".repeat(7), "..........................\n".repeat(6));
+        assert_eq!(padded.lines().count(), 20);
+        assert_eq!(ocr::decode_text(&padded), Some("EBCKY".to_string()));
+        assert_eq!(ocr::decode_text(&format!("{large}\n{}", "..............\n".repeat(40))), Some("XN".to_string()));
+
+        // Unknown shapes and unsupported heights are not guessed at
+        assert_eq!(ocr::decode_text("###\n#.#\n###\n#.#\n###\n#.#"), None);
+        assert_eq!(ocr::decode_text("#\n#"), None);
+        assert_eq!(ocr::decode_text(""), None);
+    }
+
+    #[test]
+    fn allocation_counts() {
+        // Other tests allocate on their own threads at the same time, so only lower bounds hold
+        let (buffer, stats) = measure_allocs(|| {
+            let inner = measure_allocs(|| vec![0u8; 1 << 20]).1;
+            assert!(inner.allocations >= 1 && inner.bytes >= 1 << 20 && inner.peak >= 1 << 20);
+            vec![1u8; 1 << 16]
+        });
+        assert_eq!(buffer.len(), 1 << 16);
+        assert!(stats.allocations >= 2);
+        assert!(stats.bytes >= (1 << 20) + (1 << 16));
+        assert!(stats.peak >= 1 << 20);
+
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(3 << 19), "1.5 MiB");
+    }
+
+    #[test]
+    fn assumption_checks() {
+        const ASSUMPTIONS : &[Assumption<[i64]>] = &[
+            Assumption { name: "not empty", check: |v| if v.is_empty() { Err("no values".into()) } else { Ok(()) } },
+            Assumption { name: "sorted", check: |v| match v.windows(2).position(|w| w[0] > w[1]) {
+                Some(i) => Err(format!("{} comes before {}", v[i], v[i + 1])),
+                None => Ok(()),
+            } },
+        ];
+
+        assert!(check_assumptions(1, &[1, 2, 3][..], ASSUMPTIONS));
+        assert_eq!(failed_assumptions(&[3, 1][..], ASSUMPTIONS),
+            vec!["assumption 'sorted' does not hold for this input: 3 comes before 1"]);
+        assert_eq!(failed_assumptions(&[][..], ASSUMPTIONS).len(), 1);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::Grid;
+
+// The block letters AoC draws answers with: 6 rows tall with 4-5 wide letters, and 10 rows tall
+// with 6 wide letters. Not every letter of the alphabet has ever been drawn.
+const FONT_6 : &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const FONT_10 : &[(char, &str)] = &[
+    ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+    ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+    ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+    ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+    ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+    ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+    ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+    ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+    ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+    ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+    ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+];
+
+// A letter as its lit columns, with blank columns on either side removed, each column a bitmask
+// of its lit rows
+type Glyph = Vec<u16>;
+
+// Only rows `top..=bottom`, which must be at most 16, so a tall canvas around the letters still fits
+fn columns(grid: &Grid<bool>, top: usize, bottom: usize) -> Vec<u16> {
+    (0..grid.width())
+        .map(|x| (top..=bottom).fold(0, |mask, y| mask | ((grid[(x, y)] as u16) << (y - top))))
+        .collect()
+}
+
+// Splits the picture into letters on its blank columns
+fn glyphs(columns: &[u16]) -> Vec<Glyph> {
+    columns.split(|&c| c == 0)
+        .filter(|g| !g.is_empty())
+        .map(|g| g.to_vec())
+        .collect()
+}
+
+fn font_glyph(pattern: &str) -> Glyph {
+    let grid = Grid::parse(pattern, |c| c == '#');
+    glyphs(&columns(&grid, 0, grid.height() - 1)).concat()
+}
+
+fn decode_glyph(glyph: &Glyph, font: &[(char, &str)]) -> Option<char> {
+    font.iter()
+        .find(|(_, pattern)| font_glyph(pattern) == *glyph)
+        .map(|(letter, _)| *letter)
+}
+
+// Reads the letters drawn on a grid, ignoring any blank border. None if the picture is not
+// 6 or 10 rows tall or contains a shape that is not a known letter.
+pub fn decode(grid: &Grid<bool>) -> Option<String> {
+    let lit_rows : Vec<usize> = (0..grid.height())
+        .filter(|&y| (0..grid.width()).any(|x| grid[(x, y)]))
+        .collect();
+    let (top, bottom) = (*lit_rows.first()?, *lit_rows.last()?);
+
+    let font = match bottom - top + 1 {
+        6 => FONT_6,
+        10 => FONT_10,
+        _ => return None,
+    };
+
+    glyphs(&columns(grid, top, bottom)).iter()
+        .map(|glyph| decode_glyph(glyph, font))
+        .collect()
+}
+
+// Same as decode for a picture drawn with '#' (or '█') for lit cells and anything else for dark
+pub fn decode_text(art: &str) -> Option<String> {
+    let lines : Vec<&str> = art.lines().collect();
+    let width = lines.iter().map(|l| l.chars().count()).max()?;
+    let mut grid = Grid::new(width, lines.len(), false);
+    for (y, line) in lines.iter().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            grid.set(x, y, c == '#' || c == '█');
+        }
+    }
+    decode(&grid)
+}
+
+pub fn decode_chars(grid: &Grid<char>) -> Option<String> {
+    decode_text(&grid.to_string())
+}
@@ -0,0 +1,56 @@
+use crate::{answers::AnswerLog, config::Config, Answer};
+
+// "aoc2023_day20" -> (2023, 20)
+pub fn parse_package_name(package: &str) -> Option<(u32, u32)> {
+    let (year, day) = package.strip_prefix("aoc")?.split_once("_day")?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+// Prints an answer, records it in the day's answer log and warns if it contradicts what we already know
+pub fn report_answer(package: &str, part: u32, answer: &Answer) {
+    let answer = answer.to_string();
+    println!("Part {part}: {answer}");
+
+    // Answers for some other input (e.g. one generated by `aoc scale`) say nothing about the puzzle's
+    if crate::input_path().is_some() {
+        return;
+    }
+
+    let Some((year, day)) = parse_package_name(package) else { return; };
+    let config = Config::load();
+    let path = config.answers_path(year, day);
+    let mut log = AnswerLog::load(&path);
+
+    for warning in log.check(part, &answer) {
+        eprintln!("warning: {warning}");
+    }
+
+    if log.record_produced(part, &answer) {
+        if let Err(e) = log.save(&path) {
+            eprintln!("warning: failed to write {}: {e}", path.display());
+        }
+    }
+}
+
+// answer!(1, result) - report_answer for the calling crate, with anything that converts into an Answer.
+// With --alloc-stats, what computing the answer allocated is printed as the "part N" stage.
+#[macro_export]
+macro_rules! answer {
+    ($part:expr, $answer:expr) => {{
+        let answer = $crate::alloc_stage(&format!("part {}", $part), || $crate::Answer::from($answer));
+        $crate::report_answer(env!("CARGO_PKG_NAME"), $part, &answer)
+    }};
+}
+
+// Runs a stage of a solver (e.g. parsing) and, when AOC_TIMINGS is set, prints how long it took
+// so `aoc report` can split it out of the part's time. --alloc-stats prints what it allocated.
+pub fn timed<T>(stage: &str, f: impl FnOnce() -> T) -> T {
+    if std::env::var_os("AOC_TIMINGS").is_none() {
+        return crate::alloc_stage(stage, f);
+    }
+
+    let start = std::time::Instant::now();
+    let result = crate::alloc_stage(stage, f);
+    println!("Stage {stage}: {}ns", start.elapsed().as_nanos());
+    result
+}
@@ -0,0 +1,80 @@
+use std::{fs, path::{Path, PathBuf}};
+
+pub const SNAPSHOT_EXTENSION : &str = "snap";
+pub const PENDING_EXTENSION : &str = "snap.new";
+
+pub fn snapshot_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.{SNAPSHOT_EXTENSION}"))
+}
+
+pub fn pending_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.{PENDING_EXTENSION}"))
+}
+
+// Line by line comparison, enough to see which rows of a render moved
+pub fn diff_lines(expected: &str, actual: &str) -> String {
+    let expected : Vec<&str> = expected.lines().collect();
+    let actual : Vec<&str> = actual.lines().collect();
+
+    let mut diff = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => diff += &format!("  {e}\n"),
+            (e, a) => {
+                if let Some(e) = e { diff += &format!("- {e}\n"); }
+                if let Some(a) = a { diff += &format!("+ {a}\n"); }
+            }
+        }
+    }
+    diff
+}
+
+pub enum SnapshotResult {
+    Matched,
+    // Written straight to the snapshot because updating was asked for
+    Updated,
+    // No snapshot yet, or it differs. The new render is left next to it to accept or reject.
+    Pending { diff: String },
+}
+
+// Compares a render with dir/<name>.snap. With AOC_UPDATE_SNAPSHOTS set the snapshot is
+// rewritten instead of compared.
+pub fn check_snapshot(dir: &Path, name: &str, actual: &str) -> SnapshotResult {
+    let path = snapshot_path(dir, name);
+    let pending = pending_path(dir, name);
+
+    if std::env::var_os("AOC_UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(dir).expect("Failed to create snapshot directory");
+        fs::write(&path, actual).expect("Failed to write snapshot");
+        fs::remove_file(&pending).ok();
+        return SnapshotResult::Updated;
+    }
+
+    let expected = fs::read_to_string(&path).ok();
+    if expected.as_deref() == Some(actual) {
+        fs::remove_file(&pending).ok();
+        return SnapshotResult::Matched;
+    }
+
+    fs::create_dir_all(dir).expect("Failed to create snapshot directory");
+    fs::write(&pending, actual).expect("Failed to write pending snapshot");
+    SnapshotResult::Pending { diff: diff_lines(expected.as_deref().unwrap_or(""), actual) }
+}
+
+pub fn assert_snapshot(dir: &Path, name: &str, actual: &str) {
+    if let SnapshotResult::Pending { diff } = check_snapshot(dir, name, actual) {
+        panic!("snapshot '{name}' does not match {}\n{diff}\nReview it with `aoc snapshots <year> <day>`, then accept or reject it",
+            snapshot_path(dir, name).display());
+    }
+}
+
+// assert_snapshot!("name", render) - checks a render against the crate's snapshots/name.snap
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr) => {
+        $crate::assert_snapshot(
+            &std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots"),
+            $name,
+            &$actual)
+    };
+}